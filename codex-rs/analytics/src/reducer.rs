@@ -2131,7 +2131,9 @@ fn command_action_counts(command_actions: &[CommandAction]) -> CommandActionCoun
             CommandAction::Read { .. } => counts.read += 1,
             CommandAction::ListFiles { .. } => counts.list_files += 1,
             CommandAction::Search { .. } => counts.search += 1,
-            CommandAction::Unknown { .. } => counts.unknown += 1,
+            CommandAction::Delete { .. }
+            | CommandAction::Move { .. }
+            | CommandAction::Unknown { .. } => counts.unknown += 1,
         }
     }
     counts
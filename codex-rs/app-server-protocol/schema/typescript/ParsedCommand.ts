@@ -9,4 +9,4 @@ export type ParsedCommand = { "type": "read", cmd: string, name: string,
  * be resolved against the `cwd`` that will be used to run the command
  * to derive the absolute path.
  */
-path: string, } | { "type": "list_files", cmd: string, path: string | null, } | { "type": "search", cmd: string, query: string | null, path: string | null, } | { "type": "unknown", cmd: string, };
+path: string, } | { "type": "list_files", cmd: string, path: string | null, } | { "type": "search", cmd: string, query: string | null, path: string | null, } | { "type": "delete", cmd: string, path: string, } | { "type": "move", cmd: string, from: string, to: string, } | { "type": "unknown", cmd: string, };
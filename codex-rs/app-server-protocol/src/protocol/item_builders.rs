@@ -153,6 +153,12 @@ pub(crate) fn command_actions_for_path_uri(
                 query,
                 path,
             }),
+            ParsedCommand::Delete { cmd, path } => Some(CommandAction::Delete { command: cmd, path }),
+            ParsedCommand::Move { cmd, from, to } => Some(CommandAction::Move {
+                command: cmd,
+                from,
+                to,
+            }),
             ParsedCommand::Unknown { cmd } => Some(CommandAction::Unknown { command: cmd }),
         })
         .collect()
@@ -133,6 +133,15 @@ pub enum CommandAction {
         query: Option<String>,
         path: Option<String>,
     },
+    Delete {
+        command: String,
+        path: String,
+    },
+    Move {
+        command: String,
+        from: String,
+        to: String,
+    },
     Unknown {
         command: String,
     },
@@ -196,6 +205,12 @@ impl CommandAction {
                 query,
                 path,
             } => CoreParsedCommand::Search { cmd, query, path },
+            CommandAction::Delete { command: cmd, path } => CoreParsedCommand::Delete { cmd, path },
+            CommandAction::Move {
+                command: cmd,
+                from,
+                to,
+            } => CoreParsedCommand::Move { cmd, from, to },
             CommandAction::Unknown { command: cmd } => CoreParsedCommand::Unknown { cmd },
         }
     }
@@ -215,6 +230,12 @@ impl CommandAction {
                 query,
                 path,
             },
+            CoreParsedCommand::Delete { cmd, path } => CommandAction::Delete { command: cmd, path },
+            CoreParsedCommand::Move { cmd, from, to } => CommandAction::Move {
+                command: cmd,
+                from,
+                to,
+            },
             CoreParsedCommand::Unknown { cmd } => CommandAction::Unknown { command: cmd },
         }
     }
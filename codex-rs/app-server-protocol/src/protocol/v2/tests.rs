@@ -31,6 +31,7 @@ use codex_protocol::models::ImageDetail;
 use codex_protocol::models::MessagePhase;
 use codex_protocol::models::NetworkPermissions as CoreNetworkPermissions;
 use codex_protocol::models::WebSearchAction as CoreWebSearchAction;
+use codex_protocol::parse_command::ParsedCommand as CoreParsedCommand;
 use codex_protocol::permissions::FileSystemAccessMode as CoreFileSystemAccessMode;
 use codex_protocol::permissions::FileSystemPath as CoreFileSystemPath;
 use codex_protocol::permissions::FileSystemSandboxEntry as CoreFileSystemSandboxEntry;
@@ -3094,6 +3095,41 @@ fn user_input_into_core_preserves_media_fields() {
     );
 }
 
+#[test]
+fn command_action_round_trips_deletes_and_moves() {
+    let cwd = test_path_buf("/repo").abs();
+    let delete = CoreParsedCommand::Delete {
+        cmd: "rm -rf build".to_string(),
+        path: "build".to_string(),
+    };
+    let moved = CoreParsedCommand::Move {
+        cmd: "mv a b".to_string(),
+        from: "a".to_string(),
+        to: "b".to_string(),
+    };
+
+    let action = CommandAction::from_core_with_cwd(delete.clone(), &cwd);
+    assert_eq!(
+        action,
+        CommandAction::Delete {
+            command: "rm -rf build".to_string(),
+            path: "build".to_string(),
+        }
+    );
+    assert_eq!(action.into_core(), delete);
+
+    let action = CommandAction::from_core_with_cwd(moved.clone(), &cwd);
+    assert_eq!(
+        action,
+        CommandAction::Move {
+            command: "mv a b".to_string(),
+            from: "a".to_string(),
+            to: "b".to_string(),
+        }
+    );
+    assert_eq!(action.into_core(), moved);
+}
+
 #[test]
 fn skills_list_params_serialization_uses_force_reload() {
     assert_eq!(
@@ -538,6 +538,8 @@ impl ToolRegistry {
                 ParsedCommand::Read { .. } => "read",
                 ParsedCommand::ListFiles { .. } => "list_files",
                 ParsedCommand::Search { .. } => "search",
+                ParsedCommand::Delete { .. } => "delete",
+                ParsedCommand::Move { .. } => "move",
                 ParsedCommand::Unknown { .. } => "unknown",
             });
             let category = match categories.next() {
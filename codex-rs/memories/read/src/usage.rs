@@ -42,7 +42,10 @@ pub fn memories_usage_kinds_from_command(command: &str) -> Vec<MemoriesUsageKind
         .filter_map(|command| match command {
             ParsedCommand::Read { path, .. } => get_memory_kind(path.display().to_string()),
             ParsedCommand::Search { path, .. } => path.and_then(get_memory_kind),
-            ParsedCommand::ListFiles { .. } | ParsedCommand::Unknown { .. } => None,
+            ParsedCommand::ListFiles { .. }
+            | ParsedCommand::Delete { .. }
+            | ParsedCommand::Move { .. }
+            | ParsedCommand::Unknown { .. } => None,
         })
         .collect()
 }
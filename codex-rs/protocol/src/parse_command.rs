@@ -25,6 +25,15 @@ pub enum ParsedCommand {
         query: Option<String>,
         path: Option<String>,
     },
+    Delete {
+        cmd: String,
+        path: String,
+    },
+    Move {
+        cmd: String,
+        from: String,
+        to: String,
+    },
     Unknown {
         cmd: String,
     },
@@ -45,11 +45,18 @@ pub fn try_parse_word_only_commands_sequence(tree: &Tree, src: &str) -> Option<V
         "string",
         "string_content",
         "raw_string",
+        "ansi_c_string",
         "number",
         "concatenation",
+        // single-bracket `[ ... ]` conditionals
+        "test_command",
+        "unary_expression",
+        "binary_expression",
+        "test_operator",
     ];
     // Allow only safe punctuation / operator tokens; anything else causes reject.
-    const ALLOWED_PUNCT_TOKENS: &[&str] = &["&&", "||", ";", "|", "\"", "'"];
+    const ALLOWED_PUNCT_TOKENS: &[&str] =
+        &["&&", "||", ";", "|", "\"", "'", "[", "]", "!", "=", "!="];
 
     let root = tree.root_node();
     let mut cursor = root.walk();
@@ -57,11 +64,24 @@ pub fn try_parse_word_only_commands_sequence(tree: &Tree, src: &str) -> Option<V
     let mut command_nodes = Vec::new();
     while let Some(node) = stack.pop() {
         let kind = node.kind();
+        // `cmd 2>/dev/null` only discards error output, so keep the command and
+        // drop the redirect. Any other redirection is rejected below.
+        if kind == "redirected_statement" {
+            let mut redirect_cursor = node.walk();
+            for child in node.named_children(&mut redirect_cursor) {
+                if child.kind() != "file_redirect" {
+                    stack.push(child);
+                } else if !is_stderr_to_dev_null(child, src) {
+                    return None;
+                }
+            }
+            continue;
+        }
         if node.is_named() {
             if !ALLOWED_KINDS.contains(&kind) {
                 return None;
             }
-            if kind == "command" {
+            if kind == "command" || kind == "test_command" {
                 command_nodes.push(node);
             }
         } else {
@@ -85,11 +105,12 @@ pub fn try_parse_word_only_commands_sequence(tree: &Tree, src: &str) -> Option<V
 
     let mut commands = Vec::new();
     for node in command_nodes {
-        if let Some(words) = parse_plain_command_from_node(node, src) {
-            commands.push(words);
+        let words = if node.kind() == "test_command" {
+            parse_test_command_from_node(node, src)
         } else {
-            return None;
-        }
+            parse_plain_command_from_node(node, src)
+        };
+        commands.push(words?);
     }
     Some(commands)
 }
@@ -202,6 +223,10 @@ fn parse_plain_command_from_node(cmd: tree_sitter::Node, src: &str) -> Option<Ve
                 let parsed = parse_raw_string(child, src)?;
                 words.push(parsed);
             }
+            "ansi_c_string" => {
+                let parsed = parse_ansi_c_string(child, src)?;
+                words.push(parsed);
+            }
             "concatenation" => {
                 // Handle concatenated arguments like -g"*.py"
                 let mut concatenated = String::new();
@@ -220,6 +245,10 @@ fn parse_plain_command_from_node(cmd: tree_sitter::Node, src: &str) -> Option<Ve
                             let parsed = parse_raw_string(part, src)?;
                             concatenated.push_str(&parsed);
                         }
+                        "ansi_c_string" => {
+                            let parsed = parse_ansi_c_string(part, src)?;
+                            concatenated.push_str(&parsed);
+                        }
                         _ => return None,
                     }
                 }
@@ -234,6 +263,50 @@ fn parse_plain_command_from_node(cmd: tree_sitter::Node, src: &str) -> Option<Ve
     Some(words)
 }
 
+/// Returns true for exactly `2>/dev/null` (optionally with a space before the
+/// target).
+fn is_stderr_to_dev_null(redirect: Node<'_>, src: &str) -> bool {
+    let mut cursor = redirect.walk();
+    let parts: Vec<(&str, &str)> = redirect
+        .children(&mut cursor)
+        .map(|child| {
+            (
+                child.kind(),
+                child.utf8_text(src.as_bytes()).unwrap_or_default(),
+            )
+        })
+        .collect();
+    matches!(
+        parts.as_slice(),
+        [("file_descriptor", "2"), (">", ">"), ("word", "/dev/null")]
+    )
+}
+
+/// Flattens a single-bracket `[ ... ]` conditional back into its argv, e.g.
+/// `["[", "-d", "src", "]"]`. `[[ ... ]]` is rejected because its arithmetic
+/// comparisons evaluate operands as expressions, which can run commands.
+fn parse_test_command_from_node(test: Node<'_>, src: &str) -> Option<Vec<String>> {
+    fn push_words(node: Node<'_>, src: &str, words: &mut Vec<String>) -> Option<()> {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            match child.kind() {
+                "unary_expression" | "binary_expression" => push_words(child, src, words)?,
+                "[" | "]" | "!" | "=" | "!=" | "test_operator" => {
+                    words.push(child.utf8_text(src.as_bytes()).ok()?.to_owned());
+                }
+                _ => words.push(parse_literal_shell_word(child, src)?),
+            }
+        }
+        Some(())
+    }
+
+    let mut words = Vec::new();
+    push_words(test, src, &mut words)?;
+    (words.first().map(String::as_str) == Some("[")
+        && words.last().map(String::as_str) == Some("]"))
+    .then_some(words)
+}
+
 fn parse_literal_command_from_node(cmd: Node<'_>, src: &str) -> Option<Vec<String>> {
     if cmd.kind() != "command" {
         return None;
@@ -262,6 +335,7 @@ fn parse_literal_shell_word(node: Node<'_>, src: &str) -> Option<String> {
         }
         "string" => parse_double_quoted_string(node, src),
         "raw_string" => parse_raw_string(node, src),
+        "ansi_c_string" => parse_ansi_c_string(node, src),
         "concatenation" => {
             let mut concatenated = String::new();
             let mut cursor = node.walk();
@@ -393,6 +467,39 @@ fn parse_raw_string(node: Node, src: &str) -> Option<String> {
     stripped.map(str::to_owned)
 }
 
+/// Decodes a bash `$'...'` string. Only single-character escapes such as `\t`
+/// are resolved; numeric and Unicode escapes (`\x3d`, `\101`, `\u00e9`) are
+/// rejected so an encoded flag can never hide from the safety checks.
+fn parse_ansi_c_string(node: Node, src: &str) -> Option<String> {
+    if node.kind() != "ansi_c_string" {
+        return None;
+    }
+
+    let raw = node.utf8_text(src.as_bytes()).ok()?;
+    let body = raw.strip_prefix("$'")?.strip_suffix('\'')?;
+    let mut decoded = String::with_capacity(body.len());
+    let mut chars = body.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            decoded.push(ch);
+            continue;
+        }
+        decoded.push(match chars.next()? {
+            'a' => '\u{7}',
+            'b' => '\u{8}',
+            'e' | 'E' => '\u{1b}',
+            'f' => '\u{c}',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'v' => '\u{b}',
+            escaped @ ('\\' | '\'' | '"' | '?') => escaped,
+            _ => return None,
+        });
+    }
+    Some(decoded)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -501,6 +608,52 @@ mod tests {
         assert!(parse_seq("echo \"hi $USER\"").is_none());
     }
 
+    #[test]
+    fn accepts_single_bracket_conditionals() {
+        assert_eq!(
+            parse_seq("[ -d src ] && ls src").unwrap(),
+            vec![
+                vec![
+                    "[".to_string(),
+                    "-d".to_string(),
+                    "src".to_string(),
+                    "]".to_string()
+                ],
+                vec!["ls".to_string(), "src".to_string()],
+            ]
+        );
+        assert_eq!(
+            parse_seq("[ ! -f 'a b' ]").unwrap(),
+            vec![vec![
+                "[".to_string(),
+                "!".to_string(),
+                "-f".to_string(),
+                "a b".to_string(),
+                "]".to_string(),
+            ]]
+        );
+        assert!(parse_seq("[[ -d src ]]").is_none());
+        assert!(parse_seq("[ -f $HOME ]").is_none());
+        assert!(parse_seq("[ -f $(pwd) ]").is_none());
+    }
+
+    #[test]
+    fn accepts_stderr_discarded_to_dev_null() {
+        assert_eq!(
+            parse_seq("rg foo src 2>/dev/null").unwrap(),
+            vec![vec!["rg".to_string(), "foo".to_string(), "src".to_string()]]
+        );
+        assert_eq!(
+            parse_seq("ls 2> /dev/null && pwd").unwrap(),
+            vec![vec!["ls".to_string()], vec!["pwd".to_string()]]
+        );
+        assert!(parse_seq("ls >/dev/null").is_none());
+        assert!(parse_seq("ls 2>err.log").is_none());
+        assert!(parse_seq("ls 2>>/dev/null").is_none());
+        assert!(parse_seq("ls 2>/dev/null >out.txt").is_none());
+        assert!(parse_seq("(rm x) 2>/dev/null").is_none());
+    }
+
     #[test]
     fn rejects_variable_assignment_prefix() {
         assert!(parse_seq("FOO=bar ls").is_none());
@@ -569,6 +722,29 @@ mod tests {
         assert!(parse_seq("rg -g\"${VAR}\" pattern").is_none());
     }
 
+    #[test]
+    fn decodes_ansi_c_quoted_words() {
+        assert_eq!(
+            parse_seq(r"rg $'foo\tbar' src").unwrap(),
+            vec![vec![
+                "rg".to_string(),
+                "foo\tbar".to_string(),
+                "src".to_string()
+            ]]
+        );
+        assert_eq!(
+            parse_seq(r"grep -e$'it\'s' notes.txt").unwrap(),
+            vec![vec![
+                "grep".to_string(),
+                "-eit's".to_string(),
+                "notes.txt".to_string()
+            ]]
+        );
+        assert!(parse_seq(r"rg $'--pre\x3dsh' foo").is_none());
+        assert!(parse_seq(r"rg $'\101' src").is_none());
+        assert!(parse_seq(r"rg $'\u00e9' src").is_none());
+    }
+
     #[test]
     fn rejects_concatenation_with_command_substitution() {
         // Command substitution in concatenated strings should be rejected
@@ -8,8 +8,53 @@ use crate::command_safety::is_dangerous_command::find_git_subcommand;
 use crate::command_safety::windows_safe_commands::is_safe_command_windows;
 #[cfg(windows)]
 use crate::command_safety::windows_safe_commands::is_safe_powershell_words as is_safe_powershell_words_windows;
+use std::collections::HashMap;
+
+/// Extra read-only commands an embedder trusts on top of the built-in safelist.
+///
+/// The built-in checks always apply; a command is safe if either the built-in
+/// safelist or one of the rules registered here accepts it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SafetyConfig {
+    safe_subcommands: HashMap<String, Vec<String>>,
+}
+
+impl SafetyConfig {
+    /// Trusts `program` when its first argument is one of `subcommands`, e.g.
+    /// `mytool list` for `with_safe_subcommands("mytool", ["list"])`.
+    pub fn with_safe_subcommands<I, S>(mut self, program: impl Into<String>, subcommands: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.safe_subcommands
+            .entry(program.into())
+            .or_default()
+            .extend(subcommands.into_iter().map(Into::into));
+        self
+    }
+
+    fn allows(&self, command: &[String]) -> bool {
+        let Some((program, args)) = command.split_first() else {
+            return false;
+        };
+        let Some(subcommands) = executable_name_lookup_key(program)
+            .and_then(|program| self.safe_subcommands.get(&program))
+        else {
+            return false;
+        };
+        args.first()
+            .is_some_and(|subcommand| subcommands.contains(subcommand))
+    }
+}
 
 pub fn is_known_safe_command(command: &[String]) -> bool {
+    is_known_safe_command_with(command, &SafetyConfig::default())
+}
+
+/// Like [`is_known_safe_command`], but additionally accepts the commands
+/// registered in `config`.
+pub fn is_known_safe_command_with(command: &[String], config: &SafetyConfig) -> bool {
     let command: Vec<String> = command
         .iter()
         .map(|s| {
@@ -28,7 +73,7 @@ pub fn is_known_safe_command(command: &[String]) -> bool {
         }
     }
 
-    if is_safe_to_call_with_exec(&command) {
+    if is_safe_to_call_with_exec(&command) || config.allows(&command) {
         return true;
     }
 
@@ -42,7 +87,7 @@ pub fn is_known_safe_command(command: &[String]) -> bool {
         && !all_commands.is_empty()
         && all_commands
             .iter()
-            .all(|cmd| is_safe_to_call_with_exec(cmd))
+            .all(|cmd| is_safe_to_call_with_exec(cmd) || config.allows(cmd))
     {
         return true;
     }
@@ -69,13 +114,33 @@ fn is_safe_to_call_with_exec(command: &[String]) -> bool {
         return false;
     };
 
+    // Launchers such as `open`/`xdg-open` hand their argument to whatever
+    // application is registered for it, so they must never be added here.
     match executable_name_lookup_key(cmd0).as_deref() {
         Some(cmd) if cfg!(target_os = "linux") && matches!(cmd, "numfmt" | "tac") => true,
 
+        // The shell no-op and `test` conditionals only inspect their arguments.
+        Some(":" | "[" | "test") => true,
+
+        // `set -euo pipefail`/`set +x` only toggle error handling and tracing.
+        Some("set") => is_safe_set_invocation(&command[1..]),
+
+        // `command`/`builtin` only change how the name is looked up, so the wrapped
+        // command decides; `command -v`/`-V` just describe names. `exec` replaces
+        // the shell process and is never auto-approved.
+        Some("command") => match &command[1..] {
+            [flag, ..] if flag == "-v" || flag == "-V" => true,
+            [flag, rest @ ..] if flag == "-p" => is_safe_to_call_with_exec(rest),
+            rest => is_safe_to_call_with_exec(rest),
+        },
+        Some("builtin") => is_safe_to_call_with_exec(&command[1..]),
+
         #[rustfmt::skip]
         Some(
+            "b2sum" |
             "cat" |
             "cd" |
+            "cksum" |
             "cut" |
             "echo" |
             "expr" |
@@ -84,11 +149,15 @@ fn is_safe_to_call_with_exec(command: &[String]) -> bool {
             "head" |
             "id" |
             "ls" |
+            "md5sum" |
             "nl" |
             "paste" |
             "pwd" |
             "rev" |
             "seq" |
+            "sha1sum" |
+            "sha256sum" |
+            "sha512sum" |
             "stat" |
             "tail" |
             "tr" |
@@ -153,6 +222,31 @@ fn is_safe_to_call_with_exec(command: &[String]) -> bool {
             })
         }
 
+        // Documentation lookups. Options that choose the pager or browser to
+        // run, or that write output files, are excluded. Short options are
+        // checked anywhere in a group (`-aP`), and long options also match their
+        // unambiguous abbreviations (`--pag=less`).
+        Some(cmd @ ("man" | "info" | "tldr")) => {
+            let (unsafe_short, unsafe_long): (&[char], &[&str]) = match cmd {
+                "man" => (
+                    &['P', 'H', 'C', 'X'],
+                    &["--pager", "--html", "--config-file", "--gxditview"],
+                ),
+                "info" => (&['o'], &["--output", "--dribble", "--init-file"]),
+                _ => (&['u'], &["--update", "--clear-cache", "--seed-config"]),
+            };
+
+            !command.iter().skip(1).any(|arg| {
+                if arg.starts_with("--") {
+                    let name = arg.split_once('=').map_or(arg.as_str(), |(name, _)| name);
+                    name.len() > 2 && unsafe_long.iter().any(|opt| opt.starts_with(name))
+                } else {
+                    arg.strip_prefix('-')
+                        .is_some_and(|group| group.contains(unsafe_short))
+                }
+            })
+        }
+
         // Git
         Some("git") => is_safe_git_command(command),
 
@@ -179,9 +273,10 @@ fn is_safe_to_call_with_exec(command: &[String]) -> bool {
 }
 
 pub(crate) fn is_safe_git_command(command: &[String]) -> bool {
-    let Some((subcommand_idx, subcommand)) =
-        find_git_subcommand(command, &["status", "log", "diff", "show", "branch"])
-    else {
+    let Some((subcommand_idx, subcommand)) = find_git_subcommand(
+        command,
+        &["status", "log", "diff", "show", "branch", "config"],
+    ) else {
         return false;
     };
 
@@ -198,6 +293,7 @@ pub(crate) fn is_safe_git_command(command: &[String]) -> bool {
             git_subcommand_args_are_read_only(subcommand_args)
                 && git_branch_is_read_only(subcommand_args)
         }
+        "config" => git_config_is_read_only(subcommand_args),
         other => {
             debug_assert!(false, "unexpected git subcommand from matcher: {other}");
             false
@@ -233,6 +329,36 @@ fn git_branch_is_read_only(branch_args: &[String]) -> bool {
     saw_read_only_flag
 }
 
+// Treat `git config` as safe only for explicit lookups (`--get`, `--list`, ...).
+// A bare `key value` pair, or any flag we do not recognize, may write config.
+fn git_config_is_read_only(config_args: &[String]) -> bool {
+    let mut lookup_keys_allowed = None;
+    let mut positionals = 0;
+    for arg in config_args.iter().map(String::as_str) {
+        match arg {
+            // `--get <key> [<value-pattern>]` filters by value; it never sets one.
+            "--get" | "--get-all" | "--get-regexp" if lookup_keys_allowed.is_none() => {
+                lookup_keys_allowed = Some(2);
+            }
+            "--list" | "-l" if lookup_keys_allowed.is_none() => {
+                lookup_keys_allowed = Some(0);
+            }
+            // Scope selectors and output formatting only affect what is shown.
+            "--global" | "--system" | "--local" | "--worktree" => {}
+            "--show-origin" | "--show-scope" | "--name-only" | "--null" | "-z" => {}
+            "--includes" | "--no-includes" => {}
+            _ if arg.starts_with('-') => return false,
+            _ => positionals += 1,
+        }
+    }
+
+    match lookup_keys_allowed {
+        Some(0) => positionals == 0,
+        Some(max) => (1..=max).contains(&positionals),
+        None => false,
+    }
+}
+
 #[derive(Clone, Copy)]
 enum GitOptionPattern {
     Exact(&'static str),
@@ -306,6 +432,42 @@ fn git_subcommand_args_are_read_only(args: &[String]) -> bool {
 Example
 ---------------------------------------------------------- */
 
+/// Returns true when `set` only toggles the allowlisted options `-e`, `-u`,
+/// `-x`, `-v`, or `-o errexit|nounset|pipefail|xtrace|verbose` (or their `+`
+/// forms). Others change what later words mean, e.g. `set -k` turns any
+/// `NAME=value` argument into an environment variable for the command.
+fn is_safe_set_invocation(args: &[String]) -> bool {
+    if args.is_empty() {
+        return false;
+    }
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let Some(flags) = arg
+            .strip_prefix(['-', '+'])
+            .filter(|flags| !flags.is_empty())
+        else {
+            return false;
+        };
+        for flag in flags.chars() {
+            match flag {
+                'e' | 'u' | 'x' | 'v' => {}
+                'o' => {
+                    if !args.next().is_some_and(|name| {
+                        matches!(
+                            name.as_str(),
+                            "errexit" | "nounset" | "pipefail" | "xtrace" | "verbose"
+                        )
+                    }) {
+                        return false;
+                    }
+                }
+                _ => return false,
+            }
+        }
+    }
+    true
+}
+
 /// Returns true if `arg` matches /^(\d+,)?\d+p$/
 fn is_valid_sed_n_arg(arg: Option<&str>) -> bool {
     // unwrap or bail
@@ -382,6 +544,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn checksum_commands_are_safe() {
+        for args in [
+            vec_str(&["sha256sum", "a.bin", "b.bin"]),
+            vec_str(&["md5sum", "--tag", "a.bin"]),
+            vec_str(&["cksum", "a.bin"]),
+            vec_str(&["sha256sum", "-c", "SHA256SUMS"]),
+        ] {
+            assert!(is_known_safe_command(&args), "expected {args:?} to be safe");
+        }
+    }
+
     #[test]
     fn git_branch_mutating_flags_are_not_safe() {
         assert!(!is_known_safe_command(&vec_str(&[
@@ -480,6 +654,31 @@ mod tests {
         ])));
     }
 
+    #[test]
+    fn git_show_summaries_are_safe() {
+        assert!(is_known_safe_command(&vec_str(&["git", "show"])));
+        assert!(is_known_safe_command(&vec_str(&[
+            "git", "show", "--stat", "HEAD",
+        ])));
+        assert!(is_known_safe_command(&vec_str(&[
+            "git",
+            "show",
+            "--name-only",
+            "HEAD",
+        ])));
+    }
+
+    #[test]
+    fn git_global_flags_without_overrides_are_safe() {
+        for args in [
+            vec_str(&["git", "--no-pager", "log", "-1"]),
+            vec_str(&["git", "-P", "diff"]),
+            vec_str(&["bash", "-lc", "git --no-pager show --stat HEAD"]),
+        ] {
+            assert!(is_known_safe_command(&args), "expected {args:?} to be safe");
+        }
+    }
+
     #[test]
     fn git_global_override_flags_are_not_safe() {
         assert!(!is_known_safe_command(&vec_str(&[
@@ -532,6 +731,243 @@ mod tests {
         ])));
     }
 
+    #[test]
+    fn git_config_lookups_are_safe() {
+        for args in [
+            vec_str(&["git", "config", "--get", "user.email"]),
+            vec_str(&["git", "config", "--get-all", "remote.origin.fetch"]),
+            vec_str(&["git", "config", "--global", "--list"]),
+            vec_str(&["git", "config", "-l", "--show-origin"]),
+        ] {
+            assert!(is_known_safe_command(&args), "expected {args:?} to be safe");
+        }
+
+        for args in [
+            vec_str(&["git", "config", "user.email", "x@y"]),
+            vec_str(&["git", "config", "user.email"]),
+            vec_str(&["git", "config", "--get", "--unset", "user.email"]),
+            vec_str(&["git", "config", "--list", "user.email", "x@y"]),
+            vec_str(&["git", "config", "--get"]),
+            vec_str(&["git", "config", "--edit"]),
+            vec_str(&["git", "-c", "core.pager=cat", "config", "--list"]),
+        ] {
+            assert!(
+                !is_known_safe_command(&args),
+                "expected {args:?} to be unsafe"
+            );
+        }
+    }
+
+    #[test]
+    fn git_short_and_porcelain_status_are_safe() {
+        for args in [
+            vec_str(&["git", "status", "-s"]),
+            vec_str(&["git", "status", "--porcelain", "-z"]),
+            vec_str(&["git", "status", "--porcelain=v2"]),
+        ] {
+            assert!(is_known_safe_command(&args), "expected {args:?} to be safe");
+        }
+    }
+
+    #[test]
+    fn outer_subshell_is_not_safe() {
+        assert!(!is_known_safe_command(&vec_str(&[
+            "bash", "-lc", "(rg foo)"
+        ])));
+    }
+
+    #[test]
+    fn documentation_lookups_are_safe() {
+        for args in [
+            vec_str(&["man", "ls"]),
+            vec_str(&["man", "-w", "ls"]),
+            vec_str(&["man", "-aw", "ls"]),
+            vec_str(&["man", "--where", "ls"]),
+            vec_str(&["info", "coreutils"]),
+            vec_str(&["tldr", "tar"]),
+        ] {
+            assert!(is_known_safe_command(&args), "expected {args:?} to be safe");
+        }
+
+        for args in [
+            vec_str(&["man", "-P", "sh -c 'touch x'", "ls"]),
+            vec_str(&["man", "--pager=less", "ls"]),
+            vec_str(&["man", "-Hfirefox", "ls"]),
+            vec_str(&["man", "-aP", "sh -c 'touch pwned'", "ls"]),
+            vec_str(&["man", "-aHfirefox", "ls"]),
+            vec_str(&["man", "-aX", "ls"]),
+            vec_str(&["man", "--pag=less", "ls"]),
+            vec_str(&["info", "-o", "out.txt", "coreutils"]),
+            vec_str(&["info", "-ao", "out", "ls"]),
+            vec_str(&["info", "--out", "out", "ls"]),
+            vec_str(&["tldr", "-qu"]),
+            vec_str(&["tldr", "--update"]),
+            vec_str(&["pydoc", "os"]),
+        ] {
+            assert!(
+                !is_known_safe_command(&args),
+                "expected {args:?} to be unsafe"
+            );
+        }
+    }
+
+    #[test]
+    fn shell_conditionals_are_safe() {
+        for args in [
+            vec_str(&["test", "-f", "foo.txt"]),
+            vec_str(&["bash", "-lc", "[ -d src ] && ls src"]),
+            vec_str(&["bash", "-lc", ": && ls"]),
+        ] {
+            assert!(is_known_safe_command(&args), "expected {args:?} to be safe");
+        }
+        assert!(!is_known_safe_command(&vec_str(&[
+            "bash",
+            "-lc",
+            "[[ -d src ]] && ls src"
+        ])));
+    }
+
+    #[test]
+    fn set_with_shell_options_is_safe() {
+        for args in [
+            vec_str(&["bash", "-lc", "set -euo pipefail; rg foo"]),
+            vec_str(&["bash", "-lc", "set -x && ls"]),
+            vec_str(&["bash", "-lc", "set -e -o pipefail; cat foo.txt"]),
+        ] {
+            assert!(is_known_safe_command(&args), "expected {args:?} to be safe");
+        }
+        for args in [
+            vec_str(&["bash", "-lc", "set -e; rm -rf build"]),
+            vec_str(&["set"]),
+            vec_str(&["set", "foo"]),
+            vec_str(&["set", "--"]),
+            vec_str(&["set", "-o"]),
+            vec_str(&["set", "-o", "noclobber"]),
+            vec_str(&["set", "-a"]),
+            vec_str(&["set", "-ek"]),
+        ] {
+            assert!(
+                !is_known_safe_command(&args),
+                "expected {args:?} to be unsafe"
+            );
+        }
+    }
+
+    #[test]
+    fn set_keyword_assignments_are_not_safe() {
+        // `set -k` makes `NAME=value` arguments environment variables, so rg
+        // would load an attacker-chosen config that can set `--pre`.
+        for script in [
+            "set -k; rg foo RIPGREP_CONFIG_PATH=evil.rc",
+            "set -ek; rg foo RIPGREP_CONFIG_PATH=evil.rc",
+            "set -o keyword; rg foo RIPGREP_CONFIG_PATH=evil.rc",
+        ] {
+            assert!(
+                !is_known_safe_command(&vec_str(&["bash", "-lc", script])),
+                "expected {script:?} to be unsafe"
+            );
+        }
+    }
+
+    #[test]
+    fn command_and_builtin_prefixes_defer_to_the_wrapped_command() {
+        for args in [
+            vec_str(&["command", "ls"]),
+            vec_str(&["command", "-p", "cat", "foo.txt"]),
+            vec_str(&["command", "-v", "rg"]),
+            vec_str(&["bash", "-lc", "builtin echo hi && command ls"]),
+        ] {
+            assert!(is_known_safe_command(&args), "expected {args:?} to be safe");
+        }
+        for args in [
+            vec_str(&["command", "rm", "x"]),
+            vec_str(&["builtin", "eval", "ls"]),
+            vec_str(&["command"]),
+            vec_str(&["bash", "-lc", "exec rm x"]),
+            vec_str(&["bash", "-lc", "exec cat f"]),
+        ] {
+            assert!(
+                !is_known_safe_command(&args),
+                "expected {args:?} to be unsafe"
+            );
+        }
+    }
+
+    #[test]
+    fn watch_is_never_safe() {
+        for args in [
+            vec_str(&["watch", "-n", "2", "ls"]),
+            vec_str(&["bash", "-lc", "watch -n 2 ls"]),
+        ] {
+            assert!(
+                !is_known_safe_command(&args),
+                "expected {args:?} to be unsafe"
+            );
+        }
+    }
+
+    #[test]
+    fn application_launchers_are_not_safe() {
+        for args in [
+            vec_str(&["open", "README.md"]),
+            vec_str(&["xdg-open", "."]),
+            vec_str(&["bash", "-lc", "open README.md"]),
+        ] {
+            assert!(
+                !is_known_safe_command(&args),
+                "expected {args:?} to be unsafe"
+            );
+        }
+    }
+
+    #[test]
+    fn destructive_commands_are_not_safe() {
+        for args in [
+            vec_str(&["rm", "-rf", "build"]),
+            vec_str(&["rmdir", "build"]),
+            vec_str(&["mv", "a", "b"]),
+            vec_str(&["dd", "if=/dev/zero", "of=disk.img"]),
+            vec_str(&["shred", "secret.txt"]),
+            vec_str(&["xxd", "-r", "in.hex", "out.bin"]),
+            vec_str(&["source", "setup.sh"]),
+            vec_str(&[".", "./env.sh"]),
+            vec_str(&["bash", "-lc", "source setup.sh && ls"]),
+            vec_str(&["bash", "-lc", ". ./env.sh"]),
+        ] {
+            assert!(
+                !is_known_safe_command(&args),
+                "expected {args:?} to be unsafe"
+            );
+        }
+    }
+
+    #[test]
+    fn safety_config_extends_the_builtin_safelist() {
+        let config = SafetyConfig::default().with_safe_subcommands("mytool", ["list"]);
+
+        assert!(is_known_safe_command_with(
+            &vec_str(&["mytool", "list"]),
+            &config
+        ));
+        assert!(is_known_safe_command_with(
+            &vec_str(&["bash", "-lc", "mytool list | wc -l"]),
+            &config
+        ));
+        assert!(!is_known_safe_command_with(
+            &vec_str(&["mytool", "delete"]),
+            &config
+        ));
+        assert!(!is_known_safe_command_with(&vec_str(&["mytool"]), &config));
+        assert!(!is_known_safe_command(&vec_str(&["mytool", "list"])));
+
+        // Built-in rules still apply alongside the extra allow-list.
+        assert!(is_known_safe_command_with(&vec_str(&["ls"]), &config));
+        assert!(!is_known_safe_command_with(
+            &vec_str(&["bash", "-lc", "mytool list && rm -rf /"]),
+            &config
+        ));
+    }
+
     #[test]
     fn cargo_check_is_not_safe() {
         assert!(!is_known_safe_command(&vec_str(&["cargo", "check"])));
@@ -620,6 +1056,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn grep_null_data_is_safe_unlike_rg_search_zip() {
+        // `grep -z` only changes the line delimiter; `rg -z` decompresses
+        // through external programs.
+        assert!(is_safe_to_call_with_exec(&vec_str(&[
+            "grep", "-z", "foo", "f"
+        ])));
+        assert!(!is_safe_to_call_with_exec(&vec_str(&["rg", "-z", "foo"])));
+    }
+
+    #[test]
+    fn path_qualified_programs_are_safe() {
+        assert!(is_known_safe_command(&vec_str(&["/bin/ls"])));
+        assert!(is_known_safe_command(&vec_str(&[
+            "/usr/bin/rg",
+            "foo",
+            "src"
+        ])));
+        assert!(is_known_safe_command(&vec_str(&[
+            "bash",
+            "-lc",
+            "./bin/grep TODO src"
+        ])));
+        assert!(!is_known_safe_command(&vec_str(&[
+            "/usr/bin/rg",
+            "--pre",
+            "pwned",
+            "foo"
+        ])));
+    }
+
     #[test]
     fn windows_powershell_full_path_is_safe() {
         if !cfg!(windows) {
@@ -653,6 +1120,22 @@ mod tests {
         ])));
     }
 
+    #[test]
+    fn windows_exe_suffixed_programs_are_safe() {
+        if !cfg!(windows) {
+            return;
+        }
+
+        for args in [
+            vec_str(&["git.exe", "status"]),
+            vec_str(&["GIT.EXE", "status"]),
+            vec_str(&["rg.exe", "foo", "src"]),
+        ] {
+            assert!(is_known_safe_command(&args), "expected {args:?} to be safe");
+        }
+        assert!(!is_known_safe_command(&vec_str(&["git.exe", "push"])));
+    }
+
     #[test]
     fn bash_lc_safe_examples() {
         assert!(is_known_safe_command(&vec_str(&["bash", "-lc", "ls"])));
@@ -685,6 +1168,51 @@ mod tests {
         ])));
     }
 
+    #[test]
+    fn long_bash_lc_chains_require_every_command_to_be_safe() {
+        let safe_chain = vec!["ls"; 200].join(" && ");
+        assert!(is_known_safe_command(&vec_str(&[
+            "bash",
+            "-lc",
+            &safe_chain
+        ])));
+        let unsafe_chain = format!("{safe_chain} && rm x");
+        assert!(!is_known_safe_command(&vec_str(&[
+            "bash",
+            "-lc",
+            &unsafe_chain
+        ])));
+    }
+
+    #[test]
+    fn bash_lc_ansi_c_quoting_is_vetted_after_decoding() {
+        assert!(is_known_safe_command(&vec_str(&[
+            "bash",
+            "-lc",
+            r"rg $'foo\tbar' src"
+        ])));
+        for script in [r"rg $'--pre=sh' foo", r"rg $'--pre\x3dsh' foo"] {
+            assert!(
+                !is_known_safe_command(&vec_str(&["bash", "-lc", script])),
+                "expected {script:?} to be unsafe"
+            );
+        }
+    }
+
+    #[test]
+    fn bash_lc_discarding_stderr_is_safe() {
+        assert!(is_known_safe_command(&vec_str(&[
+            "bash",
+            "-lc",
+            "rg foo src 2>/dev/null"
+        ])));
+        assert!(is_known_safe_command(&vec_str(&[
+            "bash",
+            "-lc",
+            "ls 2> /dev/null && cat foo.txt"
+        ])));
+    }
+
     #[test]
     fn bash_lc_safe_examples_with_operators() {
         assert!(is_known_safe_command(&vec_str(&[
@@ -748,6 +1276,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bash_lc_stderr_redirection_to_a_file_is_unsafe() {
+        assert!(
+            !is_known_safe_command(&vec_str(&["bash", "-lc", "ls 2> err.log"])),
+            "stderr redirection to a file should be rejected"
+        );
+    }
+
+    #[test]
+    fn bash_lc_command_substitution_is_unsafe() {
+        // Command substitutions are never vetted, even when the substituted
+        // pipeline is itself read-only.
+        assert!(
+            !is_known_safe_command(&vec_str(&["bash", "-lc", "x=$(rm -rf /); echo $x"])),
+            "Command substitution running an unsafe command must be rejected"
+        );
+        assert!(
+            !is_known_safe_command(&vec_str(&[
+                "bash",
+                "-lc",
+                "x=$(rg foo | head -n1); echo $x"
+            ])),
+            "Command substitution is not provably safe with the current parser"
+        );
+    }
+
+    #[test]
+    fn bash_lc_export_is_unsafe() {
+        // Exported variables reconfigure the commands that follow, e.g. a pager or
+        // a ripgrep config file that adds `--pre`, so `export` is never safe.
+        for script in [
+            "export FOO=1; ls",
+            "export FOO=$(rm x); ls",
+            "export GIT_PAGER=./evil; git log",
+            "export RIPGREP_CONFIG_PATH=evil.rc; rg foo",
+        ] {
+            assert!(
+                !is_known_safe_command(&vec_str(&["bash", "-lc", script])),
+                "expected {script:?} to be rejected"
+            );
+        }
+    }
+
     #[test]
     fn direct_powershell_words_use_windows_safelist() {
         let command = vec_str(&["Get-Content", "Cargo.toml"]);
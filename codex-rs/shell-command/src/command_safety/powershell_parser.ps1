@@ -151,13 +151,44 @@ function Convert-CommandElement {
     return $null
 }
 
+function Test-SafeRedirection {
+    param($redirection)
+
+    # Merging a stream into the output stream (`2>&1`, `*>&1`) never touches the filesystem.
+    if ($redirection -is [System.Management.Automation.Language.MergingRedirectionAst]) {
+        return $redirection.ToStream -eq [System.Management.Automation.Language.RedirectionStream]::Output
+    }
+
+    # Discarding a stream (`>$null`, `2>$null`) is the only file redirection we accept.
+    if ($redirection -is [System.Management.Automation.Language.FileRedirectionAst]) {
+        $location = $redirection.Location
+        return (
+            $location -is [System.Management.Automation.Language.VariableExpressionAst] -and
+            $location.VariablePath.UserPath -eq 'null'
+        )
+    }
+
+    return $false
+}
+
+function Test-SafeRedirections {
+    param($redirections)
+
+    foreach ($redirection in $redirections) {
+        if (-not (Test-SafeRedirection $redirection)) {
+            return $false
+        }
+    }
+    return $true
+}
+
 function Convert-PipelineElement {
     param($element)
 
     if ($element -is [System.Management.Automation.Language.CommandAst]) {
-        # Redirections and invocation operators make the command harder to classify safely,
+        # File redirections and invocation operators make the command harder to classify safely,
         # so reject them rather than trying to normalize them.
-        if ($element.Redirections.Count -gt 0) {
+        if (-not (Test-SafeRedirections $element.Redirections)) {
             return $null
         }
 
@@ -180,7 +211,7 @@ function Convert-PipelineElement {
     }
 
     if ($element -is [System.Management.Automation.Language.CommandExpressionAst]) {
-        if ($element.Redirections.Count -gt 0) {
+        if (-not (Test-SafeRedirections $element.Redirections)) {
             return $null
         }
 
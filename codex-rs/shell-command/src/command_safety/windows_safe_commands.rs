@@ -179,13 +179,20 @@ pub(crate) fn is_safe_powershell_words(words: &[String]) -> bool {
     match command.as_str() {
         "echo" | "write-output" | "write-host" => true, // (no redirection allowed)
         "dir" | "ls" | "get-childitem" | "gci" => true,
-        "cat" | "type" | "gc" | "get-content" => true,
+        // `-Wait` follows the file like `tail -f` and never returns.
+        "cat" | "type" | "gc" | "get-content" => !words[1..].iter().any(|w| {
+            let w = w.to_ascii_lowercase();
+            w.len() >= 4 && "-wait".starts_with(&w)
+        }),
         "select-string" | "sls" | "findstr" => true,
         "measure-object" | "measure" => true,
         "get-location" | "gl" | "pwd" => true,
         "test-path" | "tp" => true,
         "resolve-path" | "rvpa" => true,
         "select-object" | "select" => true,
+        // Format conversions only transform pipeline objects; they never write files.
+        "convertfrom-json" | "convertfrom-csv" | "convertfrom-stringdata" => true,
+        "convertto-json" | "convertto-csv" | "convertto-html" | "convertto-xml" => true,
         "get-item" => true,
 
         "git" => is_safe_git_command(words),
@@ -367,6 +374,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rejects_mutating_cmdlet_aliases() {
+        for script in [
+            "si foo bar",
+            "gc f | si x y",
+            "ni bar.txt",
+            "ri temp.log",
+            "sc notes.txt data",
+            "ac notes.txt data",
+            "cpi a b",
+            "mi a b",
+            "spps -Id 1",
+        ] {
+            assert!(
+                !is_safe_command_windows(&vec_str(&[
+                    "powershell.exe",
+                    "-NoProfile",
+                    "-Command",
+                    script,
+                ])),
+                "expected {script:?} to be rejected",
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_get_content_wait() {
+        for script in [
+            "Get-Content log -Wait",
+            "gc log -wai",
+            "cat log -Wait -Tail 10",
+        ] {
+            assert!(
+                !is_safe_command_windows(&vec_str(&[
+                    "powershell.exe",
+                    "-NoProfile",
+                    "-Command",
+                    script,
+                ])),
+                "expected {script:?} to be rejected",
+            );
+        }
+        assert!(is_safe_command_windows(&vec_str(&[
+            "powershell.exe",
+            "-NoProfile",
+            "-Command",
+            "Get-Content foo.csv -Delimiter ';'",
+        ])));
+    }
+
     #[test]
     fn rejects_git_subcommand_options_with_side_effects() {
         let results: Vec<(&str, bool)> = [
@@ -566,6 +623,78 @@ mod tests {
         ])));
     }
 
+    #[test]
+    fn accepts_stream_merging_and_discarding_redirections() {
+        for script in [
+            "Get-Content x 2>&1",
+            "Get-Content x *>&1",
+            "Get-Content x 2>$null",
+            "Get-Content x >$null",
+        ] {
+            assert!(
+                is_safe_command_windows(&vec_str(&["powershell.exe", "-Command", script])),
+                "expected {script:?} to be safe"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_redirections_to_files() {
+        for script in [
+            "Get-Content x > out.txt",
+            "Get-Content x 2> err.log",
+            "Get-Content x 2>&1 > out.txt",
+        ] {
+            assert!(
+                !is_safe_command_windows(&vec_str(&["powershell.exe", "-Command", script])),
+                "expected {script:?} to be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn accepts_format_conversion_pipelines() {
+        assert!(is_safe_command_windows(&vec_str(&[
+            "powershell.exe",
+            "-Command",
+            "Get-Content config.json | ConvertFrom-Json",
+        ])));
+        assert!(is_safe_command_windows(&vec_str(&[
+            "powershell.exe",
+            "-Command",
+            "Get-ChildItem | ConvertTo-Json",
+        ])));
+        assert!(!is_safe_command_windows(&vec_str(&[
+            "powershell.exe",
+            "-Command",
+            "Get-Content data.csv | ConvertFrom-Csv | Out-File out.txt",
+        ])));
+    }
+
+    #[test]
+    fn rejects_script_block_arguments() {
+        // Script blocks are rejected wholesale rather than vetted, so both the
+        // mutating and the read-only `ForEach-Object` forms need approval.
+        assert!(!is_safe_command_windows(&vec_str(&[
+            "powershell.exe",
+            "-Command",
+            "Get-ChildItem | ForEach-Object { Remove-Item $_ }",
+        ])));
+        assert!(!is_safe_command_windows(&vec_str(&[
+            "powershell.exe",
+            "-Command",
+            "Get-ChildItem | ForEach-Object { $_.Name }",
+        ])));
+        // The call operator on a script block is an invocation operator too.
+        for script in ["& { Remove-Item f }", "& { Get-Content f.rs }"] {
+            assert!(!is_safe_command_windows(&vec_str(&[
+                "powershell.exe",
+                "-Command",
+                script,
+            ])));
+        }
+    }
+
     #[test]
     fn rejects_dynamic_arguments() {
         assert!(!is_safe_command_windows(&vec_str(&[
@@ -2,13 +2,26 @@ use crate::bash::extract_bash_command;
 use crate::bash::try_parse_shell;
 use crate::bash::try_parse_word_only_commands_sequence;
 use crate::powershell::extract_powershell_command;
+use crate::shell_detect::ShellType;
+use crate::shell_detect::detect_shell_type;
 use codex_protocol::parse_command::ParsedCommand;
 use shlex::split as shlex_split;
-use shlex::try_join as shlex_try_join;
+use shlex::try_quote as shlex_try_quote;
+use std::path::Path;
 use std::path::PathBuf;
+use tree_sitter::Tree;
 
 pub fn shlex_join(tokens: &[String]) -> String {
-    shlex_try_join(tokens.iter().map(String::as_str))
+    tokens
+        .iter()
+        .map(|token| match token.strip_prefix("~/") {
+            // Leave a leading `~/` unquoted so the home directory still expands.
+            Some("") => Ok("~/".into()),
+            Some(rest) => shlex_try_quote(rest).map(|rest| format!("~/{rest}").into()),
+            None => shlex_try_quote(token),
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(|words| words.join(" "))
         .unwrap_or_else(|_| "<command included NUL byte>".to_string())
 }
 
@@ -28,8 +41,90 @@ pub fn extract_shell_command(command: &[String]) -> Option<(&str, &str)> {
 /// The goal of the parsed metadata is to be able to provide the user with a human readable gis
 /// of what it is doing.
 pub fn parse_command(command: &[String]) -> Vec<ParsedCommand> {
-    // Parse and then collapse consecutive duplicate commands to avoid redundant summaries.
-    let parsed = parse_command_impl(command);
+    let (parsed, _) = parse_command_traced(command);
+    parsed
+}
+
+/// The code path that produced a command's summaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParserKind {
+    /// A `bash`/`zsh`/`sh` `-c`/`-lc` script parsed with tree-sitter.
+    BashLc,
+    /// A PowerShell `-Command` script.
+    PowerShell,
+    /// A `cmd /c` invocation. There is no cmd.exe parser, so it is summarized
+    /// the same way as [`ParserKind::Generic`].
+    CmdExe,
+    /// A plain argv summarized token by token.
+    Generic,
+}
+
+/// Like [`parse_command`], but also reports which parser handled the command.
+pub fn parse_command_traced(command: &[String]) -> (Vec<ParsedCommand>, ParserKind) {
+    let (parsed, kind) = parse_command_with_kind(command, ParseMode::Strict);
+    let (parsed, _) = truncate_parsed_commands(parsed, DEFAULT_MAX_PARSED_COMMANDS);
+    (parsed, kind)
+}
+
+/// Like [`parse_command`], but keeps at most `limit` summaries instead of
+/// [`DEFAULT_MAX_PARSED_COMMANDS`]. Also returns how many were dropped.
+pub fn parse_command_with_limit(command: &[String], limit: usize) -> (Vec<ParsedCommand>, usize) {
+    let (parsed, _) = parse_command_with_kind(command, ParseMode::Strict);
+    truncate_parsed_commands(parsed, limit)
+}
+
+/// Parses a raw script string as if it were run via `bash -lc <script>`.
+///
+/// This is [`parse_shell_script`] plus the cleanup [`parse_command`] applies
+/// for display: consecutive repeats are collapsed, any unrecognized part turns
+/// the whole result into a single `Unknown` of the script, and the list is
+/// cut to [`DEFAULT_MAX_PARSED_COMMANDS`]. Prefer it when showing summaries.
+pub fn parse_bash_script(script: &str) -> Vec<ParsedCommand> {
+    let parsed = finish_parsed_commands(parse_shell_script(script), ParseMode::Strict, || {
+        script.to_string()
+    });
+    truncate_parsed_commands(parsed, DEFAULT_MAX_PARSED_COMMANDS).0
+}
+
+/// How [`parse_command_with_heuristics`] treats commands it does not recognize.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Unrecognized commands are always reported as `Unknown`.
+    #[default]
+    Strict,
+    /// An unrecognized run of a known read-only program with exactly one
+    /// file-looking operand is reported as a low-confidence `Read` of that file
+    /// (e.g. `stat config.yaml`).
+    Heuristic,
+}
+
+/// Like [`parse_command`], but lets callers opt into best-effort guesses for
+/// commands the parser does not recognize.
+pub fn parse_command_with_heuristics(command: &[String], mode: ParseMode) -> Vec<ParsedCommand> {
+    let (parsed, _) = parse_command_with_kind(command, mode);
+    truncate_parsed_commands(parsed, DEFAULT_MAX_PARSED_COMMANDS).0
+}
+
+fn parse_command_with_kind(
+    command: &[String],
+    mode: ParseMode,
+) -> (Vec<ParsedCommand>, ParserKind) {
+    let (parsed, kind) = parse_command_dispatch(command);
+    let parsed = finish_parsed_commands(parsed, mode, || unknown_command_text(command));
+    (parsed, kind)
+}
+
+/// Applies `mode` and collapses consecutive duplicate summaries. If anything is still unrecognized, the whole command is reported as
+/// one `Unknown` whose text comes from `unknown_cmd`.
+fn finish_parsed_commands(
+    parsed: Vec<ParsedCommand>,
+    mode: ParseMode,
+    unknown_cmd: impl FnOnce() -> String,
+) -> Vec<ParsedCommand> {
+    let parsed: Vec<ParsedCommand> = match mode {
+        ParseMode::Strict => parsed,
+        ParseMode::Heuristic => parsed.into_iter().map(guess_read_for_unknown).collect(),
+    };
     let mut deduped: Vec<ParsedCommand> = Vec::with_capacity(parsed.len());
     for cmd in parsed.into_iter() {
         if deduped.last().is_some_and(|prev| prev == &cmd) {
@@ -41,21 +136,99 @@ pub fn parse_command(command: &[String]) -> Vec<ParsedCommand> {
         .iter()
         .any(|cmd| matches!(cmd, ParsedCommand::Unknown { .. }))
     {
-        vec![single_unknown_for_command(command)]
+        vec![ParsedCommand::Unknown { cmd: unknown_cmd() }]
     } else {
         deduped
     }
 }
 
-fn single_unknown_for_command(command: &[String]) -> ParsedCommand {
-    if let Some((_, shell_command)) = extract_shell_command(command) {
-        ParsedCommand::Unknown {
-            cmd: shell_command.to_string(),
+/// How many summaries [`parse_command`] keeps for a single command. Use
+/// [`parse_command_with_limit`] to pick a different limit.
+pub const DEFAULT_MAX_PARSED_COMMANDS: usize = 64;
+
+/// Keeps the first `limit` summaries and returns how many were dropped.
+fn truncate_parsed_commands(
+    mut parsed: Vec<ParsedCommand>,
+    limit: usize,
+) -> (Vec<ParsedCommand>, usize) {
+    let omitted = parsed.len().saturating_sub(limit);
+    parsed.truncate(limit);
+    (parsed, omitted)
+}
+
+/// Programs that only read their file operand and that the strict parser
+/// does not already summarize. [`ParseMode::Heuristic`] guesses reads for
+/// these programs only.
+const READ_ONLY_PROGRAMS: &[&str] = &[
+    "b2sum",
+    "base64",
+    "bzcat",
+    "cksum",
+    "column",
+    "file",
+    "fold",
+    "hexdump",
+    "ldd",
+    "md5sum",
+    "nm",
+    "objdump",
+    "od",
+    "readelf",
+    "rev",
+    "sha1sum",
+    "sha256sum",
+    "sha512sum",
+    "stat",
+    "strings",
+    "tac",
+    "wc",
+    "xzcat",
+    "zcat",
+];
+
+fn guess_read_for_unknown(parsed: ParsedCommand) -> ParsedCommand {
+    let ParsedCommand::Unknown { cmd } = &parsed else {
+        return parsed;
+    };
+    let Some(tokens) = shlex_split(cmd) else {
+        return parsed;
+    };
+    if tokens
+        .iter()
+        .any(|token| matches!(token.as_str(), "&&" | "||" | "|" | ";"))
+    {
+        return parsed;
+    }
+    let Some((head, args)) = tokens.split_first() else {
+        return parsed;
+    };
+    let program = head.rsplit(['/', '\\']).next().unwrap_or(head);
+    if !READ_ONLY_PROGRAMS.contains(&program) {
+        return parsed;
+    }
+    // `key=value` operands and `-o`/`--output` name files that get written.
+    if args.iter().any(|arg| {
+        arg.contains('=') || matches!(arg.as_str(), "-o" | "--output") || arg.starts_with("-o")
+    }) {
+        return parsed;
+    }
+    match positional_operands(args, &[]).as_slice() {
+        [path] if is_pathish(path) || Path::new(path.as_str()).extension().is_some() => {
+            ParsedCommand::Read {
+                cmd: cmd.clone(),
+                name: short_display_path(path),
+                path: PathBuf::from(path.as_str()),
+            }
         }
+        _ => parsed,
+    }
+}
+
+fn unknown_command_text(command: &[String]) -> String {
+    if let Some((_, shell_command)) = extract_shell_command(command) {
+        shell_command.to_string()
     } else {
-        ParsedCommand::Unknown {
-            cmd: shlex_join(command),
-        }
+        shlex_join(command)
     }
 }
 
@@ -83,11 +256,193 @@ mod tests {
     }
 
     #[test]
-    fn git_status_is_unknown() {
+    fn heuristic_mode_guesses_reads_for_unknown_commands() {
+        let command = shlex_split_safe("stat config.yaml");
+        assert_eq!(
+            parse_command(&command),
+            vec![ParsedCommand::Unknown {
+                cmd: "stat config.yaml".to_string(),
+            }]
+        );
+        assert_eq!(
+            parse_command_with_heuristics(&command, ParseMode::Heuristic),
+            vec![ParsedCommand::Read {
+                cmd: "stat config.yaml".to_string(),
+                name: "config.yaml".to_string(),
+                path: PathBuf::from("config.yaml"),
+            }]
+        );
+        assert_eq!(
+            parse_command_with_heuristics(
+                &shlex_split_safe("/usr/bin/file ./a.out"),
+                ParseMode::Heuristic
+            ),
+            vec![ParsedCommand::Read {
+                cmd: "/usr/bin/file ./a.out".to_string(),
+                name: "a.out".to_string(),
+                path: PathBuf::from("./a.out"),
+            }]
+        );
+
+        // Commands without exactly one file-looking operand stay unknown.
+        for command in ["stat build", "stat a.yaml b.yaml", "stat --verbose"] {
+            assert_eq!(
+                parse_command_with_heuristics(&shlex_split_safe(command), ParseMode::Heuristic),
+                vec![ParsedCommand::Unknown {
+                    cmd: command.to_string(),
+                }]
+            );
+        }
+    }
+
+    #[test]
+    fn heuristic_mode_only_guesses_reads_for_read_only_programs() {
+        for command in [
+            "mytool config.yaml",
+            "touch new.txt",
+            "python build.py",
+            "/usr/bin/python3 build.py",
+            "node x.js",
+            "make target",
+            "chmod +x f",
+            "sh setup.sh",
+            "dd of=disk.img",
+            "curl -o out.html",
+            "gzip f",
+            "rustfmt f.rs",
+            "tar -xf a.tar",
+            "xxd -r in.hex",
+        ] {
+            assert_eq!(
+                parse_command_with_heuristics(&shlex_split_safe(command), ParseMode::Heuristic),
+                vec![ParsedCommand::Unknown {
+                    cmd: shlex_join(&shlex_split_safe(command)),
+                }]
+            );
+        }
+    }
+
+    #[test]
+    fn heuristic_mode_skips_written_operands() {
+        for command in [
+            "base64 -o out.b64",
+            "base64 --output out.b64",
+            "base64 --output=out.b64",
+        ] {
+            assert_eq!(
+                parse_command_with_heuristics(&shlex_split_safe(command), ParseMode::Heuristic),
+                vec![ParsedCommand::Unknown {
+                    cmd: shlex_join(&shlex_split_safe(command)),
+                }]
+            );
+        }
+    }
+
+    #[test]
+    fn sourced_scripts_are_never_reads() {
+        for command in ["source setup.sh", ". ./env.sh"] {
+            let expected = vec![ParsedCommand::Unknown {
+                cmd: command.to_string(),
+            }];
+            assert_eq!(parse_command(&shlex_split_safe(command)), expected);
+            assert_eq!(
+                parse_command_with_heuristics(&shlex_split_safe(command), ParseMode::Heuristic),
+                expected
+            );
+            assert_eq!(parse_bash_script(command), expected);
+        }
+    }
+
+    #[test]
+    fn parse_bash_script_matches_bash_lc_vector() {
+        for script in ["rg foo && cat bar", "git status", "ls -la | wc -l"] {
+            assert_eq!(
+                parse_bash_script(script),
+                parse_command(&vec_str(&["bash", "-lc", script]))
+            );
+        }
+    }
+
+    #[test]
+    fn traced_parse_reports_the_parser() {
+        assert_eq!(
+            parse_command_traced(&vec_str(&["bash", "-lc", "ls"])),
+            (
+                vec![ParsedCommand::ListFiles {
+                    cmd: "ls".to_string(),
+                    path: None,
+                }],
+                ParserKind::BashLc,
+            )
+        );
+        assert_eq!(
+            parse_command_traced(&vec_str(&["pwsh", "-c", "ls"])),
+            (
+                vec![ParsedCommand::Unknown {
+                    cmd: "ls".to_string(),
+                }],
+                ParserKind::PowerShell,
+            )
+        );
+        // `cmd /c` is reported as such but summarized like a plain argv.
+        assert_eq!(
+            parse_command_traced(&vec_str(&["cmd", "/c", "dir"])),
+            (
+                vec![ParsedCommand::Unknown {
+                    cmd: "cmd /c dir".to_string(),
+                }],
+                ParserKind::CmdExe,
+            )
+        );
+        assert_eq!(
+            parse_command_traced(&vec_str(&["cmd.exe", "/C", "type", "notes.txt"])).1,
+            ParserKind::CmdExe
+        );
+        assert_eq!(
+            parse_command_traced(&vec_str(&["ls", "-la"])).1,
+            ParserKind::Generic
+        );
+    }
+
+    #[test]
+    fn git_status_is_list_files() {
         assert_parsed(
             &vec_str(&["git", "status"]),
-            vec![ParsedCommand::Unknown {
+            vec![ParsedCommand::ListFiles {
                 cmd: "git status".to_string(),
+                path: None,
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["git", "status", "src"]),
+            vec![ParsedCommand::ListFiles {
+                cmd: "git status src".to_string(),
+                path: Some("src".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn git_short_status_is_list_files() {
+        assert_parsed(
+            &shlex_split_safe("git status -s"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "git status -s".to_string(),
+                path: None,
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("git status --porcelain"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "git status --porcelain".to_string(),
+                path: None,
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("git status --porcelain=v2 -z -- src"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "git status '--porcelain=v2' -z -- src".to_string(),
+                path: Some("src".to_string()),
             }],
         );
     }
@@ -133,13 +488,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn git_show_summary_flags_are_list_files() {
+        assert_parsed(
+            &shlex_split_safe("git show --stat HEAD"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "git show --stat HEAD".to_string(),
+                path: None,
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("git show --name-only HEAD"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "git show --name-only HEAD".to_string(),
+                path: None,
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("git show --stat HEAD -- core/src"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "git show --stat HEAD -- core/src".to_string(),
+                path: Some("core".to_string()),
+            }],
+        );
+        // Without a summary flag, `git show` prints patches and stays unrecognized.
+        assert_parsed(
+            &shlex_split_safe("git show"),
+            vec![ParsedCommand::Unknown {
+                cmd: "git show".to_string(),
+            }],
+        );
+    }
+
     #[test]
     fn handles_git_pipe_wc() {
         let inner = "git status | wc -l";
         assert_parsed(
             &vec_str(&["bash", "-lc", inner]),
-            vec![ParsedCommand::Unknown {
-                cmd: inner.to_string(),
+            vec![ParsedCommand::ListFiles {
+                cmd: "git status".to_string(),
+                path: None,
             }],
         );
     }
@@ -194,6 +582,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn search_stays_primary_through_sort_and_head() {
+        let inner = "rg -n foo src | sort -u | head -n 20";
+        assert_parsed(
+            &vec_str(&["bash", "-lc", inner]),
+            vec![ParsedCommand::Search {
+                cmd: "rg -n foo src".to_string(),
+                query: Some("foo".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+        let inner = "rg -n foo src | sort -t: -k 2 | uniq -c";
+        assert_parsed(
+            &vec_str(&["bash", "-lc", inner]),
+            vec![ParsedCommand::Search {
+                cmd: "rg -n foo src".to_string(),
+                query: Some("foo".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn xxd_reverse_is_not_a_read() {
+        // `xxd -r` writes its second operand, so it must not look like a plain read.
+        assert_parsed(
+            &shlex_split_safe("xxd -r in.hex out.bin"),
+            vec![ParsedCommand::Unknown {
+                cmd: "xxd -r in.hex out.bin".to_string(),
+            }],
+        );
+    }
+
+    #[test]
+    fn chained_searches_stay_separate() {
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "rg foo src | grep bar"]),
+            vec![
+                ParsedCommand::Search {
+                    cmd: "rg foo src".to_string(),
+                    query: Some("foo".to_string()),
+                    path: Some("src".to_string()),
+                },
+                ParsedCommand::Search {
+                    cmd: "grep bar".to_string(),
+                    query: Some("bar".to_string()),
+                    path: None,
+                },
+            ],
+        );
+    }
+
     #[test]
     fn supports_rg_files_with_path_and_pipe() {
         let inner = "rg --files webview/src | sed -n";
@@ -411,6 +851,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn nested_bash_lc_is_parsed_recursively() {
+        assert_parsed(
+            &vec_str(&["bash", "-lc", r#"bash -lc "rg foo""#]),
+            vec![ParsedCommand::Search {
+                cmd: "bash -lc 'rg foo'".to_string(),
+                query: Some("foo".to_string()),
+                path: None,
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "cd src && sh -c 'cat lib.rs'"]),
+            vec![ParsedCommand::Read {
+                cmd: "cat lib.rs".to_string(),
+                name: "lib.rs".to_string(),
+                path: PathBuf::from("src/lib.rs"),
+            }],
+        );
+    }
+
+    #[test]
+    fn deeply_nested_bash_lc_stops_recursing() {
+        let mut script = "rg foo".to_string();
+        for _ in 0..=MAX_NESTED_SHELL_DEPTH + 1 {
+            script = shlex_join(&vec_str(&["bash", "-lc", &script]));
+        }
+        assert_parsed(
+            &vec_str(&["bash", "-lc", &script]),
+            vec![ParsedCommand::Unknown {
+                cmd: script.clone(),
+            }],
+        );
+    }
+
     #[test]
     fn bash_cd_then_bar_is_same_as_bar() {
         // Ensure a leading `cd` inside bash -lc is dropped when followed by another command.
@@ -434,6 +908,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bash_cd_to_absolute_path_replaces_the_cwd() {
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "cd /abs/path && cat rel.txt"]),
+            vec![ParsedCommand::Read {
+                cmd: "cat rel.txt".to_string(),
+                name: "rel.txt".to_string(),
+                path: PathBuf::from("/abs/path/rel.txt"),
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "cd /a && cd /b && cat f"]),
+            vec![ParsedCommand::Read {
+                cmd: "cat f".to_string(),
+                name: "f".to_string(),
+                path: PathBuf::from("/b/f"),
+            }],
+        );
+    }
+
     #[test]
     fn supports_ls_with_pipe() {
         let inner = "ls -la | sed -n '1,120p'";
@@ -476,6 +970,31 @@ mod tests {
                 path: Some(".".to_string()),
             }],
         );
+        assert_parsed(
+            &shlex_split_safe("du -sh target"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "du -sh target".to_string(),
+                path: Some("target".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn supports_df() {
+        assert_parsed(
+            &shlex_split_safe("df -h /"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "df -h /".to_string(),
+                path: Some("/".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("df -t ext4 ."),
+            vec![ParsedCommand::ListFiles {
+                cmd: "df -t ext4 .".to_string(),
+                path: Some(".".to_string()),
+            }],
+        );
     }
 
     #[test]
@@ -517,6 +1036,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cat_piped_into_head_or_tail_is_read() {
+        for inner in ["cat foo.rs | head -n 40", "cat foo.rs | tail -n 40"] {
+            assert_parsed(
+                &vec_str(&["bash", "-lc", inner]),
+                vec![ParsedCommand::Read {
+                    cmd: "cat foo.rs".to_string(),
+                    name: "foo.rs".to_string(),
+                    path: PathBuf::from("foo.rs"),
+                }],
+            );
+        }
+    }
+
     #[test]
     fn supports_tail_n_plus() {
         let inner = "tail -n +522 README.md";
@@ -667,6 +1200,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rg_and_fd_queries_with_slashes_are_not_shortened() {
+        assert_parsed(
+            &shlex_split_safe("rg 'src/main.rs' ."),
+            vec![ParsedCommand::Search {
+                cmd: "rg src/main.rs .".to_string(),
+                query: Some("src/main.rs".to_string()),
+                path: Some(".".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("fd 'a/b' dir"),
+            vec![ParsedCommand::Search {
+                cmd: "fd a/b dir".to_string(),
+                query: Some("a/b".to_string()),
+                path: Some("dir".to_string()),
+            }],
+        );
+    }
+
     #[test]
     fn supports_grep_weird_backtick_in_query() {
         assert_parsed(
@@ -750,18 +1303,135 @@ mod tests {
     }
 
     #[test]
-    fn awk_behavior() {
-        assert!(is_small_formatting_command(&shlex_split_safe(
-            "awk '{print $1}'"
-        )));
-        assert!(!is_small_formatting_command(&shlex_split_safe(
-            "awk '{print $1}' Cargo.toml"
-        )));
-        assert!(!is_small_formatting_command(&shlex_split_safe(
-            "awk -f script.awk Cargo.toml"
-        )));
-    }
-
+    fn leading_set_options_are_dropped() {
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "set -euo pipefail; rg foo"]),
+            vec![ParsedCommand::Search {
+                cmd: "rg foo".to_string(),
+                query: Some("foo".to_string()),
+                path: None,
+            }],
+        );
+    }
+
+    #[test]
+    fn long_scripts_are_capped_after_dedup() {
+        let script = (0..200)
+            .map(|i| format!("cat f{i}"))
+            .collect::<Vec<_>>()
+            .join(" && ");
+        let read = |i: usize| ParsedCommand::Read {
+            cmd: format!("cat f{i}"),
+            name: format!("f{i}"),
+            path: PathBuf::from(format!("f{i}")),
+        };
+        let expected: Vec<ParsedCommand> = (0..DEFAULT_MAX_PARSED_COMMANDS).map(read).collect();
+        assert_eq!(parse_bash_script(&script), expected);
+
+        // Callers can pick the limit and learn how many summaries were dropped.
+        let command = vec_str(&["bash", "-lc", &script]);
+        let expected: Vec<ParsedCommand> = (0..10).map(read).collect();
+        assert_eq!(parse_command_with_limit(&command, 10), (expected, 190));
+        assert_eq!(parse_command_with_limit(&command, 500).1, 0);
+
+        // Repeats collapse before the cap applies.
+        let repeated = vec!["cat f"; 200].join(" && ");
+        assert_eq!(
+            parse_bash_script(&repeated),
+            vec![ParsedCommand::Read {
+                cmd: "cat f".to_string(),
+                name: "f".to_string(),
+                path: PathBuf::from("f"),
+            }]
+        );
+    }
+
+    #[test]
+    fn sort_with_an_output_file_is_not_a_read() {
+        for command in [
+            "sort -o out data.txt",
+            "sort -oout data.txt",
+            "sort -no out data.txt",
+            "sort --output out data.txt",
+            "sort --output=out data.txt",
+        ] {
+            assert_parsed(
+                &shlex_split_safe(command),
+                vec![ParsedCommand::Unknown {
+                    cmd: shlex_join(&shlex_split_safe(command)),
+                }],
+            );
+            assert!(!is_small_formatting_command(&shlex_split_safe(command)));
+        }
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "cat data.txt | sort -o out"]),
+            vec![ParsedCommand::Unknown {
+                cmd: "cat data.txt | sort -o out".to_string(),
+            }],
+        );
+        // `-k` takes the rest of its cluster as a value, so this `o` is not `-o`.
+        assert_parsed(
+            &shlex_split_safe("sort -k1o data.txt"),
+            vec![ParsedCommand::Read {
+                cmd: "sort -k1o data.txt".to_string(),
+                name: "data.txt".to_string(),
+                path: PathBuf::from("data.txt"),
+            }],
+        );
+    }
+
+    #[test]
+    fn text_filters_with_a_file_are_reads() {
+        assert_parsed(
+            &shlex_split_safe("sort data.txt"),
+            vec![ParsedCommand::Read {
+                cmd: "sort data.txt".to_string(),
+                name: "data.txt".to_string(),
+                path: PathBuf::from("data.txt"),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("sort -t , -k 2 data.csv"),
+            vec![ParsedCommand::Read {
+                cmd: "sort -t ',' -k 2 data.csv".to_string(),
+                name: "data.csv".to_string(),
+                path: PathBuf::from("data.csv"),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("cut -d : -f 1 /etc/passwd"),
+            vec![ParsedCommand::Read {
+                cmd: "cut -d : -f 1 /etc/passwd".to_string(),
+                name: "passwd".to_string(),
+                path: PathBuf::from("/etc/passwd"),
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "cat f | sort"]),
+            vec![ParsedCommand::Read {
+                cmd: "cat f".to_string(),
+                name: "f".to_string(),
+                path: PathBuf::from("f"),
+            }],
+        );
+        assert!(!is_small_formatting_command(&shlex_split_safe(
+            "uniq -c log.txt"
+        )));
+    }
+
+    #[test]
+    fn awk_behavior() {
+        assert!(is_small_formatting_command(&shlex_split_safe(
+            "awk '{print $1}'"
+        )));
+        assert!(!is_small_formatting_command(&shlex_split_safe(
+            "awk '{print $1}' Cargo.toml"
+        )));
+        assert!(!is_small_formatting_command(&shlex_split_safe(
+            "awk -f script.awk Cargo.toml"
+        )));
+    }
+
     #[test]
     fn head_behavior() {
         // No args -> small formatting
@@ -852,6 +1522,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn nl_number_format_is_not_the_path() {
+        for cmd in ["nl -ba -nrz Cargo.toml", "nl -ba -n rz Cargo.toml"] {
+            assert_parsed(
+                &shlex_split_safe(cmd),
+                vec![ParsedCommand::Read {
+                    cmd: cmd.to_string(),
+                    name: "Cargo.toml".to_string(),
+                    path: PathBuf::from("Cargo.toml"),
+                }],
+            );
+        }
+    }
+
     #[test]
     fn supports_sed_n() {
         let inner = "sed -n '2000,2200p' tui/src/history_cell.rs";
@@ -878,6 +1562,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn awk_line_range_is_a_read() {
+        let inner = "awk 'NR>=10 && NR<=20' f.rs";
+        assert_parsed(
+            &vec_str(&["bash", "-lc", inner]),
+            vec![ParsedCommand::Read {
+                cmd: inner.to_string(),
+                name: "f.rs".to_string(),
+                path: PathBuf::from("f.rs"),
+            }],
+        );
+    }
+
+    #[test]
+    fn jq_reads_its_primary_input_file() {
+        assert_parsed(
+            &shlex_split_safe("jq --slurpfile x aux.json '.' main.json"),
+            vec![ParsedCommand::Read {
+                cmd: "jq --slurpfile x aux.json . main.json".to_string(),
+                name: "main.json".to_string(),
+                path: PathBuf::from("main.json"),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("jq -r --arg name v '.[$name]' data/config.json"),
+            vec![ParsedCommand::Read {
+                cmd: "jq -r --arg name v '.[$name]' data/config.json".to_string(),
+                name: "config.json".to_string(),
+                path: PathBuf::from("data/config.json"),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("jq '.name'"),
+            vec![ParsedCommand::Unknown {
+                cmd: "jq .name".to_string(),
+            }],
+        );
+    }
+
     #[test]
     fn filters_out_printf() {
         let inner =
@@ -905,6 +1628,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn discarded_stderr_is_ignored_in_summaries() {
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "rg foo src 2>/dev/null"]),
+            vec![ParsedCommand::Search {
+                cmd: "rg foo src".to_string(),
+                query: Some("foo".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn bare_searches_have_empty_summaries() {
+        for (args, cmd) in [
+            (vec_str(&["rg"]), "rg"),
+            (vec_str(&["grep"]), "grep"),
+            (vec_str(&["bash", "-lc", "yes | rg"]), "rg"),
+        ] {
+            assert_parsed(
+                &args,
+                vec![ParsedCommand::Search {
+                    cmd: cmd.to_string(),
+                    query: None,
+                    path: None,
+                }],
+            );
+        }
+    }
+
     #[test]
     fn supports_sed_n_then_nl_as_search() {
         // Ensure `sed -n '<range>' <file> | nl -ba` is summarized as a search for that file.
@@ -933,6 +1686,67 @@ mod tests {
         );
     }
 
+    #[test]
+    fn path_qualified_programs_use_their_basename() {
+        assert_parsed(
+            &vec_str(&["/usr/bin/rg", "foo", "src"]),
+            vec![ParsedCommand::Search {
+                cmd: "/usr/bin/rg foo src".to_string(),
+                query: Some("foo".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("./bin/grep TODO"),
+            vec![ParsedCommand::Search {
+                cmd: "./bin/grep TODO".to_string(),
+                query: Some("TODO".to_string()),
+                path: None,
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["/bin/ls"]),
+            vec![ParsedCommand::ListFiles {
+                cmd: "/bin/ls".to_string(),
+                path: None,
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["./scripts/cat", "foo.txt"]),
+            vec![ParsedCommand::Read {
+                cmd: "./scripts/cat foo.txt".to_string(),
+                name: "foo.txt".to_string(),
+                path: PathBuf::from("foo.txt"),
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["./bin/search", "foo"]),
+            vec![ParsedCommand::Unknown {
+                cmd: "./bin/search foo".to_string(),
+            }],
+        );
+    }
+
+    #[test]
+    fn exe_suffixed_programs_use_their_bare_name() {
+        assert_parsed(
+            &vec_str(&["rg.exe", "foo", "src"]),
+            vec![ParsedCommand::Search {
+                cmd: "rg.exe foo src".to_string(),
+                query: Some("foo".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["grep.EXE", "TODO"]),
+            vec![ParsedCommand::Search {
+                cmd: "grep.EXE TODO".to_string(),
+                query: Some("TODO".to_string()),
+                path: None,
+            }],
+        );
+    }
+
     #[test]
     fn ls_with_glob() {
         assert_parsed(
@@ -1032,185 +1846,1430 @@ mod tests {
                 path: PathBuf::from("README.md"),
             }],
         );
-    }
-
-    #[test]
-    fn grep_with_query_and_path() {
+    }
+
+    #[test]
+    fn grep_with_query_and_path() {
+        assert_parsed(
+            &shlex_split_safe("grep -R TODO src"),
+            vec![ParsedCommand::Search {
+                cmd: "grep -R TODO src".to_string(),
+                query: Some("TODO".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn supports_ag_ack_pt_rga() {
+        assert_parsed(
+            &shlex_split_safe("ag TODO src"),
+            vec![ParsedCommand::Search {
+                cmd: "ag TODO src".to_string(),
+                query: Some("TODO".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("ack TODO src"),
+            vec![ParsedCommand::Search {
+                cmd: "ack TODO src".to_string(),
+                query: Some("TODO".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("pt TODO src"),
+            vec![ParsedCommand::Search {
+                cmd: "pt TODO src".to_string(),
+                query: Some("TODO".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("rga TODO src"),
+            vec![ParsedCommand::Search {
+                cmd: "rga TODO src".to_string(),
+                query: Some("TODO".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn ag_ack_pt_files_with_matches_flags_are_search() {
+        assert_parsed(
+            &shlex_split_safe("ag -l TODO src"),
+            vec![ParsedCommand::Search {
+                cmd: "ag -l TODO src".to_string(),
+                query: Some("TODO".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("ack -l TODO src"),
+            vec![ParsedCommand::Search {
+                cmd: "ack -l TODO src".to_string(),
+                query: Some("TODO".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("pt -l TODO src"),
+            vec![ParsedCommand::Search {
+                cmd: "pt -l TODO src".to_string(),
+                query: Some("TODO".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn rg_with_equals_style_flags() {
+        assert_parsed(
+            &shlex_split_safe("rg --colors=never -n foo src"),
+            vec![ParsedCommand::Search {
+                cmd: "rg '--colors=never' -n foo src".to_string(),
+                query: Some("foo".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn cat_with_double_dash_and_sed_ranges() {
+        // cat -- <file> should be treated as a read of that file
+        assert_parsed(
+            &shlex_split_safe("cat -- ./-strange-file-name"),
+            vec![ParsedCommand::Read {
+                cmd: "cat -- ./-strange-file-name".to_string(),
+                name: "-strange-file-name".to_string(),
+                path: PathBuf::from("./-strange-file-name"),
+            }],
+        );
+
+        // sed -n <range> <file> should be treated as a read of <file>
+        assert_parsed(
+            &shlex_split_safe("sed -n '12,20p' Cargo.toml"),
+            vec![ParsedCommand::Read {
+                cmd: "sed -n '12,20p' Cargo.toml".to_string(),
+                name: "Cargo.toml".to_string(),
+                path: PathBuf::from("Cargo.toml"),
+            }],
+        );
+    }
+
+    #[test]
+    fn reads_files_with_unusual_names() {
+        assert_parsed(
+            &shlex_split_safe(r#"cat "my file.txt""#),
+            vec![ParsedCommand::Read {
+                cmd: "cat 'my file.txt'".to_string(),
+                name: "my file.txt".to_string(),
+                path: PathBuf::from("my file.txt"),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("cat café.md"),
+            vec![ParsedCommand::Read {
+                cmd: "cat 'café.md'".to_string(),
+                name: "café.md".to_string(),
+                path: PathBuf::from("café.md"),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("head -n 5 -- --weird"),
+            vec![ParsedCommand::Read {
+                cmd: "head -n 5 -- --weird".to_string(),
+                name: "--weird".to_string(),
+                path: PathBuf::from("--weird"),
+            }],
+        );
+    }
+
+    #[test]
+    fn stdin_dash_operand_is_not_a_read() {
+        assert_parsed(
+            &shlex_split_safe("cat -"),
+            vec![ParsedCommand::Unknown {
+                cmd: "cat -".to_string(),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("cat -- -"),
+            vec![ParsedCommand::Unknown {
+                cmd: "cat -- -".to_string(),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("sed -n 1,5p -"),
+            vec![ParsedCommand::Unknown {
+                cmd: "sed -n '1,5p' -".to_string(),
+            }],
+        );
+    }
+
+    #[test]
+    fn destructive_commands_are_delete_and_move() {
+        assert_parsed(
+            &shlex_split_safe("rm -rf build"),
+            vec![ParsedCommand::Delete {
+                cmd: "rm -rf build".to_string(),
+                path: "build".to_string(),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("mv a b"),
+            vec![ParsedCommand::Move {
+                cmd: "mv a b".to_string(),
+                from: "a".to_string(),
+                to: "b".to_string(),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("mv -t dest a b"),
+            vec![
+                ParsedCommand::Move {
+                    cmd: "mv -t dest a b".to_string(),
+                    from: "a".to_string(),
+                    to: "dest".to_string(),
+                },
+                ParsedCommand::Move {
+                    cmd: "mv -t dest a b".to_string(),
+                    from: "b".to_string(),
+                    to: "dest".to_string(),
+                },
+            ],
+        );
+        // `dd of=...` overwrites its output rather than deleting it.
+        assert_parsed(
+            &shlex_split_safe("dd if=/dev/zero of=disk.img bs=1M count=1"),
+            vec![ParsedCommand::Unknown {
+                cmd: "dd 'if=/dev/zero' 'of=disk.img' 'bs=1M' 'count=1'".to_string(),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("shred -n 3 secret.txt"),
+            vec![ParsedCommand::Delete {
+                cmd: "shred -n 3 secret.txt".to_string(),
+                path: "secret.txt".to_string(),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("rm -f"),
+            vec![ParsedCommand::Unknown {
+                cmd: "rm -f".to_string(),
+            }],
+        );
+    }
+
+    #[test]
+    fn delete_and_move_paths_follow_cd() {
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "cd build && rm -rf out"]),
+            vec![ParsedCommand::Delete {
+                cmd: "rm -rf out".to_string(),
+                path: "build/out".to_string(),
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "cd src && mv a.rs /tmp/a.rs"]),
+            vec![ParsedCommand::Move {
+                cmd: "mv a.rs /tmp/a.rs".to_string(),
+                from: "src/a.rs".to_string(),
+                to: "/tmp/a.rs".to_string(),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("cd build && rm -rf out"),
+            vec![ParsedCommand::Delete {
+                cmd: "rm -rf out".to_string(),
+                path: "build/out".to_string(),
+            }],
+        );
+    }
+
+    #[test]
+    fn watch_summarizes_the_watched_command() {
+        assert_parsed(
+            &shlex_split_safe("watch -n 2 ls"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "ls".to_string(),
+                path: None,
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("watch -d 'cat status.txt'"),
+            vec![ParsedCommand::Read {
+                cmd: "cat status.txt".to_string(),
+                name: "status.txt".to_string(),
+                path: PathBuf::from("status.txt"),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("watch -n 2 'ls | wc -l'"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "ls".to_string(),
+                path: None,
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("watch 'rg -n TODO src | head -n 5'"),
+            vec![ParsedCommand::Search {
+                cmd: "rg -n TODO src".to_string(),
+                query: Some("TODO".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("watch -n 5"),
+            vec![ParsedCommand::Unknown {
+                cmd: "watch -n 5".to_string(),
+            }],
+        );
+    }
+
+    #[test]
+    fn head_and_tail_read_each_file() {
+        assert_parsed(
+            &shlex_split_safe("head -q -n 10 a.txt b.txt"),
+            vec![
+                ParsedCommand::Read {
+                    cmd: "head -q -n 10 a.txt b.txt".to_string(),
+                    name: "a.txt".to_string(),
+                    path: PathBuf::from("a.txt"),
+                },
+                ParsedCommand::Read {
+                    cmd: "head -q -n 10 a.txt b.txt".to_string(),
+                    name: "b.txt".to_string(),
+                    path: PathBuf::from("b.txt"),
+                },
+            ],
+        );
+        assert_parsed(
+            &shlex_split_safe("tail --verbose -n +5 log.txt"),
+            vec![ParsedCommand::Read {
+                cmd: "tail --verbose -n +5 log.txt".to_string(),
+                name: "log.txt".to_string(),
+                path: PathBuf::from("log.txt"),
+            }],
+        );
+    }
+
+    #[test]
+    fn checksum_commands_read_each_file() {
+        assert_parsed(
+            &shlex_split_safe("sha256sum a.bin b.bin"),
+            vec![
+                ParsedCommand::Read {
+                    cmd: "sha256sum a.bin b.bin".to_string(),
+                    name: "a.bin".to_string(),
+                    path: PathBuf::from("a.bin"),
+                },
+                ParsedCommand::Read {
+                    cmd: "sha256sum a.bin b.bin".to_string(),
+                    name: "b.bin".to_string(),
+                    path: PathBuf::from("b.bin"),
+                },
+            ],
+        );
+        assert_parsed(
+            &shlex_split_safe("md5sum --tag -b dist/app.tar.gz"),
+            vec![ParsedCommand::Read {
+                cmd: "md5sum --tag -b dist/app.tar.gz".to_string(),
+                name: "app.tar.gz".to_string(),
+                path: PathBuf::from("dist/app.tar.gz"),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("cksum -a sha256 file.txt"),
+            vec![ParsedCommand::Read {
+                cmd: "cksum -a sha256 file.txt".to_string(),
+                name: "file.txt".to_string(),
+                path: PathBuf::from("file.txt"),
+            }],
+        );
+    }
+
+    #[test]
+    fn drop_trailing_nl_in_pipeline() {
+        // When an `nl` stage has only flags, it should be dropped from the summary
+        assert_parsed(
+            &shlex_split_safe("rg --files | nl -ba"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "rg --files".to_string(),
+                path: None,
+            }],
+        );
+    }
+
+    #[test]
+    fn ls_with_time_style_and_path() {
+        assert_parsed(
+            &shlex_split_safe("ls --time-style=long-iso ./dist"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "ls '--time-style=long-iso' ./dist".to_string(),
+                // short_display_path drops "dist" and shows "." as the last useful segment
+                path: Some(".".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn ls_sort_values_are_not_the_path() {
+        assert_parsed(
+            &shlex_split_safe("ls --sort=size src"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "ls '--sort=size' src".to_string(),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("ls --sort time -l app"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "ls --sort time -l app".to_string(),
+                path: Some("app".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("eza --sort time ."),
+            vec![ParsedCommand::ListFiles {
+                cmd: "eza --sort time .".to_string(),
+                path: Some(".".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn fd_file_finder_variants() {
+        assert_parsed(
+            &shlex_split_safe("fd -t f src/"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "fd -t f src/".to_string(),
+                path: Some("src".to_string()),
+            }],
+        );
+
+        // fd with query and path should capture both
+        assert_parsed(
+            &shlex_split_safe("fd main src"),
+            vec![ParsedCommand::Search {
+                cmd: "fd main src".to_string(),
+                query: Some("main".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn find_basic_name_filter() {
+        assert_parsed(
+            &shlex_split_safe("find . -name '*.rs'"),
+            vec![ParsedCommand::Search {
+                cmd: "find . -name '*.rs'".to_string(),
+                query: Some("*.rs".to_string()),
+                path: Some(".".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn find_option_values_are_not_the_path() {
+        assert_parsed(
+            &shlex_split_safe("find -maxdepth 2 src -name '*.rs'"),
+            vec![ParsedCommand::Search {
+                cmd: "find -maxdepth 2 src -name '*.rs'".to_string(),
+                query: Some("*.rs".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("find -depth 1 docs"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "find -depth 1 docs".to_string(),
+                path: Some("docs".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn diff_summarizes_both_operands() {
+        assert_parsed(
+            &shlex_split_safe("diff -r src/ old/"),
+            vec![
+                ParsedCommand::ListFiles {
+                    cmd: "diff -r src/ old/".to_string(),
+                    path: Some("src".to_string()),
+                },
+                ParsedCommand::ListFiles {
+                    cmd: "diff -r src/ old/".to_string(),
+                    path: Some("old".to_string()),
+                },
+            ],
+        );
+        assert_parsed(
+            &shlex_split_safe("diff -ruN --exclude target a b"),
+            vec![
+                ParsedCommand::ListFiles {
+                    cmd: "diff -ruN --exclude target a b".to_string(),
+                    path: Some("a".to_string()),
+                },
+                ParsedCommand::ListFiles {
+                    cmd: "diff -ruN --exclude target a b".to_string(),
+                    path: Some("b".to_string()),
+                },
+            ],
+        );
+        assert_parsed(
+            &shlex_split_safe("diff -u old/main.rs src/main.rs"),
+            vec![
+                ParsedCommand::Read {
+                    cmd: "diff -u old/main.rs src/main.rs".to_string(),
+                    name: "main.rs".to_string(),
+                    path: PathBuf::from("old/main.rs"),
+                },
+                ParsedCommand::Read {
+                    cmd: "diff -u old/main.rs src/main.rs".to_string(),
+                    name: "main.rs".to_string(),
+                    path: PathBuf::from("src/main.rs"),
+                },
+            ],
+        );
+        assert_parsed(
+            &shlex_split_safe("diff a.txt"),
+            vec![ParsedCommand::Unknown {
+                cmd: "diff a.txt".to_string(),
+            }],
+        );
+    }
+
+    #[test]
+    fn find_without_predicates_lists_files() {
+        assert_parsed(
+            &shlex_split_safe("find src"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "find src".to_string(),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("find src -name '*.rs'"),
+            vec![ParsedCommand::Search {
+                cmd: "find src -name '*.rs'".to_string(),
+                query: Some("*.rs".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn find_type_only_path() {
+        assert_parsed(
+            &shlex_split_safe("find src -type f"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "find src -type f".to_string(),
+                path: Some("src".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn counting_pipelines_keep_the_listing_command() {
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "ls | wc -l"]),
+            vec![ParsedCommand::ListFiles {
+                cmd: "ls".to_string(),
+                path: None,
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "find src -type f | wc -l"]),
+            vec![ParsedCommand::ListFiles {
+                cmd: "find src -type f".to_string(),
+                path: Some("src".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn null_separated_output_flags_are_not_query_or_path() {
+        for args in [
+            vec_str(&["grep", "-lZ", "foo", "src"]),
+            vec_str(&["grep", "-l", "--null", "foo", "src"]),
+            vec_str(&["rg", "--null", "foo", "src"]),
+            vec_str(&["rg", "-0", "foo", "src"]),
+        ] {
+            assert_parsed(
+                &args,
+                vec![ParsedCommand::Search {
+                    cmd: args.join(" "),
+                    query: Some("foo".to_string()),
+                    path: Some("src".to_string()),
+                }],
+            );
+        }
+    }
+
+    #[test]
+    fn grep_pattern_type_flags_are_not_the_query() {
+        for flag in ["-P", "-E", "-F", "-G"] {
+            assert_parsed(
+                &vec_str(&["grep", flag, "pat", "file"]),
+                vec![ParsedCommand::Search {
+                    cmd: format!("grep {flag} pat file"),
+                    query: Some("pat".to_string()),
+                    path: Some("file".to_string()),
+                }],
+            );
+        }
+        assert_parsed(
+            &shlex_split_safe(r"grep -P '\d+' file"),
+            vec![ParsedCommand::Search {
+                cmd: r#"grep -P "\\d+" file"#.to_string(),
+                query: Some(r"\d+".to_string()),
+                path: Some("file".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn color_flag_values_are_not_queries() {
+        assert_parsed(
+            &shlex_split_safe("grep --color always TODO src"),
+            vec![ParsedCommand::Search {
+                cmd: "grep --color always TODO src".to_string(),
+                query: Some("TODO".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("grep --color TODO src"),
+            vec![ParsedCommand::Search {
+                cmd: "grep --color TODO src".to_string(),
+                query: Some("TODO".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("rg --color never foo lib"),
+            vec![ParsedCommand::Search {
+                cmd: "rg --color never foo lib".to_string(),
+                query: Some("foo".to_string()),
+                path: Some("lib".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn shell_comments_are_ignored() {
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "rg foo # comment"]),
+            vec![ParsedCommand::Search {
+                cmd: "rg foo".to_string(),
+                query: Some("foo".to_string()),
+                path: None,
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "ls && # note"]),
+            vec![ParsedCommand::ListFiles {
+                cmd: "ls".to_string(),
+                path: None,
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "# find the config\ncat Cargo.toml"]),
+            vec![ParsedCommand::Read {
+                cmd: "cat Cargo.toml".to_string(),
+                name: "Cargo.toml".to_string(),
+                path: PathBuf::from("Cargo.toml"),
+            }],
+        );
+    }
+
+    #[test]
+    fn rg_explicit_regexp_may_start_with_dash() {
+        assert_parsed(
+            &shlex_split_safe("rg -e '-v' src"),
+            vec![ParsedCommand::Search {
+                cmd: "rg -e -v src".to_string(),
+                query: Some("-v".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("rg -n --regexp=-foo src/lib.rs"),
+            vec![ParsedCommand::Search {
+                cmd: "rg -n '--regexp=-foo' src/lib.rs".to_string(),
+                query: Some("-foo".to_string()),
+                path: Some("lib.rs".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn ansi_c_quoted_queries_are_decoded() {
+        assert_parsed(
+            &vec_str(&["bash", "-lc", r"rg $'foo\tbar' src"]),
+            vec![ParsedCommand::Search {
+                cmd: "rg 'foo\tbar' src".to_string(),
+                query: Some("foo\tbar".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn boolean_match_flags_are_not_queries() {
+        for program in ["rg", "grep", "ag"] {
+            for flag in ["-w", "-x", "-i"] {
+                let cmd = format!("{program} {flag} foo src");
+                assert_parsed(
+                    &shlex_split_safe(&cmd),
+                    vec![ParsedCommand::Search {
+                        cmd: cmd.clone(),
+                        query: Some("foo".to_string()),
+                        path: Some("src".to_string()),
+                    }],
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn search_path_globs_keep_their_shape() {
+        assert_parsed(
+            &shlex_split_safe("rg foo 'src/**/*.rs'"),
+            vec![ParsedCommand::Search {
+                cmd: "rg foo 'src/**/*.rs'".to_string(),
+                query: Some("foo".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("grep TODO 'lib/**'"),
+            vec![ParsedCommand::Search {
+                cmd: "grep TODO 'lib/**'".to_string(),
+                query: Some("TODO".to_string()),
+                path: Some("lib".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn search_over_several_paths_shows_the_first() {
+        assert_parsed(
+            &shlex_split_safe("rg foo a.rs b.rs"),
+            vec![ParsedCommand::Search {
+                cmd: "rg foo a.rs b.rs".to_string(),
+                query: Some("foo".to_string()),
+                path: Some("a.rs".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("rg foo src tests"),
+            vec![ParsedCommand::Search {
+                cmd: "rg foo src tests".to_string(),
+                query: Some("foo".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("grep -n foo a.rs b.rs"),
+            vec![ParsedCommand::Search {
+                cmd: "grep -n foo a.rs b.rs".to_string(),
+                query: Some("foo".to_string()),
+                path: Some("a.rs".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn attached_regexp_patterns_are_queries() {
+        for cmd in [
+            "rg -efoo src",
+            "rg --regexp=foo src",
+            "grep -efoo src",
+            "grep --regexp=foo src",
+        ] {
+            assert_parsed(
+                &shlex_split_safe(cmd),
+                vec![ParsedCommand::Search {
+                    cmd: shlex_join(&shlex_split_safe(cmd)),
+                    query: Some("foo".to_string()),
+                    path: Some("src".to_string()),
+                }],
+            );
+        }
+    }
+
+    #[test]
+    fn rg_multiple_regexps_are_alternatives() {
+        assert_parsed(
+            &shlex_split_safe("rg -e foo -e bar src"),
+            vec![ParsedCommand::Search {
+                cmd: "rg -e foo -e bar src".to_string(),
+                query: Some("foo|bar".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("rg -e foo --regexp bar --regexp=baz"),
+            vec![ParsedCommand::Search {
+                cmd: "rg -e foo --regexp bar '--regexp=baz'".to_string(),
+                query: Some("foo|bar|baz".to_string()),
+                path: None,
+            }],
+        );
+    }
+
+    #[test]
+    fn rg_files_globs_only_hint_included_directories() {
+        assert_parsed(
+            &shlex_split_safe("rg --files -g '!target'"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "rg --files -g '!target'".to_string(),
+                path: None,
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("rg --files -g 'src/**'"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "rg --files -g 'src/**'".to_string(),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("rg --files -g '*.rs'"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "rg --files -g '*.rs'".to_string(),
+                path: None,
+            }],
+        );
+    }
+
+    #[test]
+    fn home_relative_paths_are_kept_verbatim() {
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "cat ~/notes.txt"]),
+            vec![ParsedCommand::Read {
+                cmd: "cat ~/notes.txt".to_string(),
+                name: "notes.txt".to_string(),
+                path: PathBuf::from("~/notes.txt"),
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "head -n 10 ~/.bashrc"]),
+            vec![ParsedCommand::Read {
+                cmd: "head -n 10 ~/.bashrc".to_string(),
+                name: ".bashrc".to_string(),
+                path: PathBuf::from("~/.bashrc"),
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "cd src && cat ~/notes.txt"]),
+            vec![ParsedCommand::Read {
+                cmd: "cat ~/notes.txt".to_string(),
+                name: "notes.txt".to_string(),
+                path: PathBuf::from("~/notes.txt"),
+            }],
+        );
+        // Only the part after `~/` is quoted, so the tilde still expands.
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "cat ~/'my notes.txt'"]),
+            vec![ParsedCommand::Read {
+                cmd: "cat ~/'my notes.txt'".to_string(),
+                name: "my notes.txt".to_string(),
+                path: PathBuf::from("~/my notes.txt"),
+            }],
+        );
+    }
+
+    #[test]
+    fn grep_include_exclude_globs_are_not_queries() {
+        assert_parsed(
+            &shlex_split_safe("grep -r --include='*.rs' TODO src"),
+            vec![ParsedCommand::Search {
+                cmd: "grep -r '--include=*.rs' TODO src".to_string(),
+                query: Some("TODO".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("grep -r --include '*.rs' --exclude-dir target TODO src"),
+            vec![ParsedCommand::Search {
+                cmd: "grep -r --include '*.rs' --exclude-dir target TODO src".to_string(),
+                query: Some("TODO".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn pagers_are_dropped_after_git_commands() {
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "git show --stat HEAD | less"]),
+            vec![ParsedCommand::ListFiles {
+                cmd: "git show --stat HEAD".to_string(),
+                path: None,
+            }],
+        );
+        // `git diff` has no dedicated summary yet, so the script stays unknown.
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "git diff | less -R"]),
+            vec![ParsedCommand::Unknown {
+                cmd: "git diff | less -R".to_string(),
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "less README.md"]),
+            vec![ParsedCommand::Read {
+                cmd: "less README.md".to_string(),
+                name: "README.md".to_string(),
+                path: PathBuf::from("README.md"),
+            }],
+        );
+    }
+
+    #[test]
+    fn empty_search_patterns_have_no_query() {
+        assert_parsed(
+            &shlex_split_safe("rg '' src"),
+            vec![ParsedCommand::Search {
+                cmd: "rg '' src".to_string(),
+                query: None,
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("grep '' ."),
+            vec![ParsedCommand::Search {
+                cmd: "grep '' .".to_string(),
+                query: None,
+                path: Some(".".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("fd '' src"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "fd '' src".to_string(),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("find src -name ''"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "find src -name ''".to_string(),
+                path: Some("src".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn count_only_flag_takes_no_value() {
+        assert_parsed(
+            &shlex_split_safe("rg -c foo src"),
+            vec![ParsedCommand::Search {
+                cmd: "rg -c foo src".to_string(),
+                query: Some("foo".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("grep -c foo f"),
+            vec![ParsedCommand::Search {
+                cmd: "grep -c foo f".to_string(),
+                query: Some("foo".to_string()),
+                path: Some("f".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn rg_boolean_flags_are_not_query_or_path() {
+        for flag in [
+            "-I",
+            "--no-filename",
+            "-N",
+            "-H",
+            "--no-heading",
+            "--heading",
+            "-o",
+            "--only-matching",
+            "-v",
+            "--invert-match",
+            "-w",
+            "-x",
+            "-F",
+            "-S",
+            "-s",
+            "-i",
+        ] {
+            assert_parsed(
+                &vec_str(&["rg", flag, "foo", "src"]),
+                vec![ParsedCommand::Search {
+                    cmd: format!("rg {flag} foo src"),
+                    query: Some("foo".to_string()),
+                    path: Some("src".to_string()),
+                }],
+            );
+        }
+    }
+
+    #[test]
+    fn rg_multiline_queries_are_preserved() {
+        assert_parsed(
+            &shlex_split_safe(r"rg -U 'foo\nbar' src"),
+            vec![ParsedCommand::Search {
+                cmd: r#"rg -U "foo\\nbar" src"#.to_string(),
+                query: Some(r"foo\nbar".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["rg", "--multiline", "foo\nbar", "src"]),
+            vec![ParsedCommand::Search {
+                cmd: "rg --multiline 'foo\nbar' src".to_string(),
+                query: Some("foo\nbar".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn rg_path_list_files_are_not_the_path() {
+        for (input, cmd) in [
+            (
+                "rg --files-from paths.txt foo",
+                "rg --files-from paths.txt foo",
+            ),
+            (
+                "rg --files-from=paths.txt foo",
+                "rg '--files-from=paths.txt' foo",
+            ),
+        ] {
+            assert_parsed(
+                &shlex_split_safe(input),
+                vec![ParsedCommand::Search {
+                    cmd: cmd.to_string(),
+                    query: Some("foo".to_string()),
+                    path: None,
+                }],
+            );
+        }
+    }
+
+    #[test]
+    fn rg_preprocessor_values_are_not_the_query() {
+        let expected = |cmd: &str| {
+            vec![ParsedCommand::Search {
+                cmd: cmd.to_string(),
+                query: Some("foo".to_string()),
+                path: Some("src".to_string()),
+            }]
+        };
+        assert_parsed(
+            &shlex_split_safe("rg --pre=pwned foo src"),
+            expected("rg '--pre=pwned' foo src"),
+        );
+        assert_parsed(
+            &shlex_split_safe("rg --pre pwned foo src"),
+            expected("rg --pre pwned foo src"),
+        );
+        assert_parsed(
+            &shlex_split_safe("rg --hostname-bin pwned foo src"),
+            expected("rg --hostname-bin pwned foo src"),
+        );
+    }
+
+    #[test]
+    fn rg_pattern_file_is_not_the_query() {
+        assert_parsed(
+            &shlex_split_safe("rg -f patterns.txt src"),
+            vec![ParsedCommand::Search {
+                cmd: "rg -f patterns.txt src".to_string(),
+                query: None,
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("rg --file=patterns.txt"),
+            vec![ParsedCommand::Search {
+                cmd: "rg '--file=patterns.txt'".to_string(),
+                query: None,
+                path: None,
+            }],
+        );
+    }
+
+    #[test]
+    fn null_data_flag_is_a_plain_search() {
+        assert_parsed(
+            &shlex_split_safe("grep -z foo f"),
+            vec![ParsedCommand::Search {
+                cmd: "grep -z foo f".to_string(),
+                query: Some("foo".to_string()),
+                path: Some("f".to_string()),
+            }],
+        );
+        // `rg -z` searches compressed files and is never auto-approved, but it
+        // still summarizes as a search.
+        assert_parsed(
+            &shlex_split_safe("rg -z foo"),
+            vec![ParsedCommand::Search {
+                cmd: "rg -z foo".to_string(),
+                query: Some("foo".to_string()),
+                path: None,
+            }],
+        );
+    }
+
+    #[test]
+    fn sed_range_like_queries_stay_searches() {
+        assert_parsed(
+            &shlex_split_safe("rg '1,5p' src"),
+            vec![ParsedCommand::Search {
+                cmd: "rg '1,5p' src".to_string(),
+                query: Some("1,5p".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("grep -n '1,200p' notes.txt"),
+            vec![ParsedCommand::Search {
+                cmd: "grep -n '1,200p' notes.txt".to_string(),
+                query: Some("1,200p".to_string()),
+                path: Some("notes.txt".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn stdin_dash_search_has_no_path() {
+        assert_parsed(
+            &shlex_split_safe("rg foo -"),
+            vec![ParsedCommand::Search {
+                cmd: "rg foo -".to_string(),
+                query: Some("foo".to_string()),
+                path: None,
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("grep bar -"),
+            vec![ParsedCommand::Search {
+                cmd: "grep bar -".to_string(),
+                query: Some("bar".to_string()),
+                path: None,
+            }],
+        );
         assert_parsed(
-            &shlex_split_safe("grep -R TODO src"),
+            &shlex_split_safe("grep -- bar -"),
             vec![ParsedCommand::Search {
-                cmd: "grep -R TODO src".to_string(),
-                query: Some("TODO".to_string()),
-                path: Some("src".to_string()),
+                cmd: "grep -- bar -".to_string(),
+                query: Some("bar".to_string()),
+                path: None,
             }],
         );
     }
 
     #[test]
-    fn supports_ag_ack_pt_rga() {
+    fn outer_subshell_is_unwrapped_for_display() {
         assert_parsed(
-            &shlex_split_safe("ag TODO src"),
+            &vec_str(&["bash", "-lc", "(rg foo)"]),
             vec![ParsedCommand::Search {
-                cmd: "ag TODO src".to_string(),
-                query: Some("TODO".to_string()),
-                path: Some("src".to_string()),
+                cmd: "rg foo".to_string(),
+                query: Some("foo".to_string()),
+                path: None,
             }],
         );
         assert_parsed(
-            &shlex_split_safe("ack TODO src"),
-            vec![ParsedCommand::Search {
-                cmd: "ack TODO src".to_string(),
-                query: Some("TODO".to_string()),
-                path: Some("src".to_string()),
+            &vec_str(&["bash", "-lc", "(rg foo) && ls"]),
+            vec![ParsedCommand::Unknown {
+                cmd: "(rg foo) && ls".to_string(),
             }],
         );
+    }
+
+    #[test]
+    fn ls_directory_globs_keep_the_glob_parent() {
         assert_parsed(
-            &shlex_split_safe("pt TODO src"),
-            vec![ParsedCommand::Search {
-                cmd: "pt TODO src".to_string(),
-                query: Some("TODO".to_string()),
-                path: Some("src".to_string()),
+            &shlex_split_safe("ls -d bazel-*/external"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "ls -d 'bazel-*/external'".to_string(),
+                path: Some("bazel-*/external".to_string()),
             }],
         );
         assert_parsed(
-            &shlex_split_safe("rga TODO src"),
-            vec![ParsedCommand::Search {
-                cmd: "rga TODO src".to_string(),
-                query: Some("TODO".to_string()),
-                path: Some("src".to_string()),
+            &shlex_split_safe("ls -d */"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "ls -d '*/'".to_string(),
+                path: Some("*".to_string()),
             }],
         );
     }
 
     #[test]
-    fn ag_ack_pt_files_with_matches_flags_are_search() {
+    fn bazel_queries_search_for_the_target_pattern() {
         assert_parsed(
-            &shlex_split_safe("ag -l TODO src"),
+            &shlex_split_safe("bazel query //src/..."),
             vec![ParsedCommand::Search {
-                cmd: "ag -l TODO src".to_string(),
-                query: Some("TODO".to_string()),
-                path: Some("src".to_string()),
+                cmd: "bazel query //src/...".to_string(),
+                query: Some("//src/...".to_string()),
+                path: None,
             }],
         );
         assert_parsed(
-            &shlex_split_safe("ack -l TODO src"),
+            &shlex_split_safe("bazel cquery --output label 'deps(//app:bin)'"),
             vec![ParsedCommand::Search {
-                cmd: "ack -l TODO src".to_string(),
-                query: Some("TODO".to_string()),
-                path: Some("src".to_string()),
+                cmd: "bazel cquery --output label 'deps(//app:bin)'".to_string(),
+                query: Some("deps(//app:bin)".to_string()),
+                path: None,
             }],
         );
         assert_parsed(
-            &shlex_split_safe("pt -l TODO src"),
-            vec![ParsedCommand::Search {
-                cmd: "pt -l TODO src".to_string(),
-                query: Some("TODO".to_string()),
-                path: Some("src".to_string()),
+            &shlex_split_safe("bazel info"),
+            vec![ParsedCommand::Unknown {
+                cmd: "bazel info".to_string(),
             }],
         );
     }
 
     #[test]
-    fn rg_with_equals_style_flags() {
+    fn git_global_options_precede_the_subcommand() {
         assert_parsed(
-            &shlex_split_safe("rg --colors=never -n foo src"),
+            &shlex_split_safe("git --no-pager grep -n foo src"),
             vec![ParsedCommand::Search {
-                cmd: "rg '--colors=never' -n foo src".to_string(),
+                cmd: "git --no-pager grep -n foo src".to_string(),
                 query: Some("foo".to_string()),
                 path: Some("src".to_string()),
             }],
         );
+        assert_parsed(
+            &shlex_split_safe("git -C /repo diff -- a.rs"),
+            vec![ParsedCommand::Read {
+                cmd: "git -C /repo diff -- a.rs".to_string(),
+                name: "a.rs".to_string(),
+                path: PathBuf::from("a.rs"),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("git -c core.quotepath=off status -s"),
+            vec![ParsedCommand::ListFiles {
+                cmd: "git -c 'core.quotepath=off' status -s".to_string(),
+                path: None,
+            }],
+        );
     }
 
     #[test]
-    fn cat_with_double_dash_and_sed_ranges() {
-        // cat -- <file> should be treated as a read of that file
+    fn git_diff_pathspecs_are_reads() {
+        let cmd = "git diff -- core/src/a.rs core/src/b.rs";
         assert_parsed(
-            &shlex_split_safe("cat -- ./-strange-file-name"),
+            &shlex_split_safe(cmd),
+            vec![
+                ParsedCommand::Read {
+                    cmd: cmd.to_string(),
+                    name: "a.rs".to_string(),
+                    path: PathBuf::from("core/src/a.rs"),
+                },
+                ParsedCommand::Read {
+                    cmd: cmd.to_string(),
+                    name: "b.rs".to_string(),
+                    path: PathBuf::from("core/src/b.rs"),
+                },
+            ],
+        );
+        assert_parsed(
+            &shlex_split_safe("git show abc123 -- README.md"),
             vec![ParsedCommand::Read {
-                cmd: "cat -- ./-strange-file-name".to_string(),
-                name: "-strange-file-name".to_string(),
-                path: PathBuf::from("./-strange-file-name"),
+                cmd: "git show abc123 -- README.md".to_string(),
+                name: "README.md".to_string(),
+                path: PathBuf::from("README.md"),
             }],
         );
-
-        // sed -n <range> <file> should be treated as a read of <file>
         assert_parsed(
-            &shlex_split_safe("sed -n '12,20p' Cargo.toml"),
-            vec![ParsedCommand::Read {
-                cmd: "sed -n '12,20p' Cargo.toml".to_string(),
-                name: "Cargo.toml".to_string(),
-                path: PathBuf::from("Cargo.toml"),
+            &shlex_split_safe("git diff HEAD"),
+            vec![ParsedCommand::Unknown {
+                cmd: "git diff HEAD".to_string(),
             }],
         );
     }
 
     #[test]
-    fn drop_trailing_nl_in_pipeline() {
-        // When an `nl` stage has only flags, it should be dropped from the summary
+    fn command_prefixes_summarize_the_wrapped_command() {
         assert_parsed(
-            &shlex_split_safe("rg --files | nl -ba"),
+            &vec_str(&["command", "ls"]),
             vec![ParsedCommand::ListFiles {
-                cmd: "rg --files".to_string(),
+                cmd: "ls".to_string(),
                 path: None,
             }],
         );
-    }
-
-    #[test]
-    fn ls_with_time_style_and_path() {
         assert_parsed(
-            &shlex_split_safe("ls --time-style=long-iso ./dist"),
-            vec![ParsedCommand::ListFiles {
-                cmd: "ls '--time-style=long-iso' ./dist".to_string(),
-                // short_display_path drops "dist" and shows "." as the last useful segment
-                path: Some(".".to_string()),
+            &vec_str(&["bash", "-lc", "exec cat f"]),
+            vec![ParsedCommand::Read {
+                cmd: "exec cat f".to_string(),
+                name: "f".to_string(),
+                path: PathBuf::from("f"),
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "exec rm x"]),
+            vec![ParsedCommand::Delete {
+                cmd: "rm x".to_string(),
+                path: "x".to_string(),
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["command", "-v", "rg"]),
+            vec![ParsedCommand::Unknown {
+                cmd: "command -v rg".to_string(),
             }],
         );
     }
 
     #[test]
-    fn fd_file_finder_variants() {
+    fn echo_globs_are_listings() {
         assert_parsed(
-            &shlex_split_safe("fd -t f src/"),
+            &vec_str(&["bash", "-lc", "echo src/*.rs"]),
             vec![ParsedCommand::ListFiles {
-                cmd: "fd -t f src/".to_string(),
+                cmd: "echo 'src/*.rs'".to_string(),
                 path: Some("src".to_string()),
             }],
         );
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "echo *.md"]),
+            vec![ParsedCommand::ListFiles {
+                cmd: "echo '*.md'".to_string(),
+                path: Some("*.md".to_string()),
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "echo */"]),
+            vec![ParsedCommand::ListFiles {
+                cmd: "echo '*/'".to_string(),
+                path: Some("*".to_string()),
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "echo hello"]),
+            vec![ParsedCommand::Unknown {
+                cmd: "echo hello".to_string(),
+            }],
+        );
+    }
 
-        // fd with query and path should capture both
+    #[test]
+    fn echo_messages_with_glob_characters_are_not_listings() {
         assert_parsed(
-            &shlex_split_safe("fd main src"),
+            &vec_str(&["bash", "-lc", "echo \"done?\"; rg foo src"]),
             vec![ParsedCommand::Search {
-                cmd: "fd main src".to_string(),
-                query: Some("main".to_string()),
+                cmd: "rg foo src".to_string(),
+                query: Some("foo".to_string()),
                 path: Some("src".to_string()),
             }],
         );
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "echo 'Is it ready?' && ls"]),
+            vec![ParsedCommand::ListFiles {
+                cmd: "ls".to_string(),
+                path: None,
+            }],
+        );
+        for message in [
+            "echo done?",
+            "echo 'ready?!'",
+            "echo '*** build failed ***'",
+        ] {
+            assert_parsed(
+                &shlex_split_safe(message),
+                vec![ParsedCommand::Unknown {
+                    cmd: shlex_join(&shlex_split_safe(message)),
+                }],
+            );
+        }
     }
 
     #[test]
-    fn find_basic_name_filter() {
+    fn cat_globs_are_reads_of_the_pattern() {
         assert_parsed(
-            &shlex_split_safe("find . -name '*.rs'"),
-            vec![ParsedCommand::Search {
-                cmd: "find . -name '*.rs'".to_string(),
-                query: Some("*.rs".to_string()),
-                path: Some(".".to_string()),
+            &vec_str(&["bash", "-lc", "cat src/*.rs"]),
+            vec![ParsedCommand::Read {
+                cmd: "cat 'src/*.rs'".to_string(),
+                name: "*.rs".to_string(),
+                path: PathBuf::from("src/*.rs"),
             }],
         );
     }
 
     #[test]
-    fn find_type_only_path() {
+    fn shell_conditionals_are_dropped_from_summaries() {
         assert_parsed(
-            &shlex_split_safe("find src -type f"),
+            &vec_str(&["bash", "-lc", "[ -d src ] && ls src"]),
             vec![ParsedCommand::ListFiles {
-                cmd: "find src -type f".to_string(),
+                cmd: "ls src".to_string(),
                 path: Some("src".to_string()),
             }],
         );
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "test -f foo.txt && cat foo.txt"]),
+            vec![ParsedCommand::Read {
+                cmd: "cat foo.txt".to_string(),
+                name: "foo.txt".to_string(),
+                path: PathBuf::from("foo.txt"),
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "test -f foo.txt"]),
+            vec![ParsedCommand::Unknown {
+                cmd: "test -f foo.txt".to_string(),
+            }],
+        );
+    }
+
+    #[test]
+    fn simple_script_fast_path_matches_tree_sitter_parse() {
+        for script in [
+            "ls",
+            "rg foo src",
+            "rg  foo   src",
+            "ls -la src",
+            "cat README.md",
+            "sed -n 1,5p foo.txt",
+            "nl -ba foo.rs",
+            "git status",
+            "head",
+            "echo 1",
+            "true",
+            ":",
+            "bash -lc ls",
+        ] {
+            let fast = parse_shell_script(script);
+            let tree = try_parse_shell(script).expect("script should parse");
+            let words = try_parse_word_only_commands_sequence(&tree, script)
+                .expect("script should be word-only");
+            assert!(
+                simple_script_words(script).is_some(),
+                "expected {script:?} to take the fast path"
+            );
+            assert_eq!(
+                fast,
+                summarize_shell_commands(script, words, /*shell_depth*/ 0),
+                "{script:?}"
+            );
+        }
+        for script in [
+            "export FOO",
+            "for",
+            "FOO=1 ls",
+            "ls > out",
+            "ls $HOME",
+            "echo 'a b'",
+            "ls *.rs",
+            "ls ~",
+            "ls\nrg foo",
+        ] {
+            assert_eq!(simple_script_words(script), None, "{script:?}");
+        }
     }
 
     #[test]
@@ -1274,14 +3333,20 @@ mod tests {
 }
 
 pub fn parse_command_impl(command: &[String]) -> Vec<ParsedCommand> {
+    parse_command_dispatch(command).0
+}
+
+/// Routes `command` to the parser that handles it and reports which one ran.
+fn parse_command_dispatch(command: &[String]) -> (Vec<ParsedCommand>, ParserKind) {
     if let Some(commands) = parse_shell_lc_commands(command) {
-        return commands;
+        return (commands, ParserKind::BashLc);
     }
 
     if let Some((_, script)) = extract_powershell_command(command) {
-        return vec![ParsedCommand::Unknown {
+        let commands = vec![ParsedCommand::Unknown {
             cmd: script.to_string(),
         }];
+        return (commands, ParserKind::PowerShell);
     }
 
     let normalized = normalize_tokens(command);
@@ -1310,30 +3375,30 @@ pub fn parse_command_impl(command: &[String]) -> Vec<ParsedCommand> {
             }
             continue;
         }
-        let parsed = summarize_main_tokens(tokens);
-        let parsed = match parsed {
-            ParsedCommand::Read { cmd, name, path } => {
-                if let Some(base) = &cwd {
-                    let full = join_paths(base, &path.to_string_lossy());
-                    ParsedCommand::Read {
-                        cmd,
-                        name,
-                        path: PathBuf::from(full),
-                    }
-                } else {
-                    ParsedCommand::Read { cmd, name, path }
-                }
-            }
-            other => other,
-        };
-        commands.push(parsed);
+        for parsed in summarize_command_segment(tokens, /*shell_depth*/ 0) {
+            commands.push(resolve_against_cwd(parsed, cwd.as_deref()));
+        }
     }
 
     while let Some(next) = simplify_once(&commands) {
         commands = next;
     }
 
-    commands
+    let kind = if is_cmd_exe_invocation(command) {
+        ParserKind::CmdExe
+    } else {
+        ParserKind::Generic
+    };
+    (commands, kind)
+}
+
+/// Whether `command` runs a script through `cmd /c`.
+fn is_cmd_exe_invocation(command: &[String]) -> bool {
+    matches!(
+        command,
+        [shell, flag, ..]
+            if detect_shell_type(shell) == Some(ShellType::Cmd) && flag.eq_ignore_ascii_case("/c")
+    )
 }
 
 fn simplify_once(commands: &[ParsedCommand]) -> Option<Vec<ParsedCommand>> {
@@ -1362,6 +3427,22 @@ fn simplify_once(commands: &[ParsedCommand]) -> Option<Vec<ParsedCommand>> {
         return Some(out);
     }
 
+    // `:`/`test ...`/`[ ... ]` guards and `set -e` options && ...rest => ...rest
+    if let Some(idx) = commands.iter().position(|pc| match pc {
+        ParsedCommand::Unknown { cmd } => shlex_split(cmd).is_some_and(|t| {
+            matches!(
+                t.first().map(String::as_str),
+                Some(":" | "test" | "[" | "set")
+            )
+        }),
+        _ => false,
+    }) {
+        let mut out = Vec::with_capacity(commands.len() - 1);
+        out.extend_from_slice(&commands[..idx]);
+        out.extend_from_slice(&commands[idx + 1..]);
+        return Some(out);
+    }
+
     // cmd || true => cmd
     if let Some(idx) = commands
         .iter()
@@ -1523,13 +3604,57 @@ fn short_display_path(path: &str) -> String {
     // Normalize separators and drop any trailing slash for display.
     let normalized = path.replace('\\', "/");
     let trimmed = normalized.trim_end_matches('/');
-    let mut parts = trimmed.split('/').rev().filter(|p| {
+    if trimmed.is_empty() {
+        // The filesystem root (`/`) has no segment to shorten.
+        return normalized;
+    }
+    let parts: Vec<&str> = trimmed.split('/').collect();
+    let Some(leaf_idx) = parts.iter().rposition(|p| {
         !p.is_empty() && *p != "build" && *p != "dist" && *p != "node_modules" && *p != "src"
+    }) else {
+        return trimmed.to_string();
+    };
+    // Keep a glob parent (`bazel-*/external`): the leaf alone hides which
+    // entries are being matched.
+    match leaf_idx.checked_sub(1).map(|idx| parts[idx]) {
+        Some(parent) if parent.contains(['*', '?', '[']) => {
+            format!("{parent}/{}", parts[leaf_idx])
+        }
+        _ => parts[leaf_idx].to_string(),
+    }
+}
+
+/// Returns the literal directories leading a glob (`src` for `src/**/*.rs`),
+/// or `None` when the glob starts with a wildcard or has none.
+fn glob_literal_dir(glob: &str) -> Option<String> {
+    let parts: Vec<&str> = glob.split('/').collect();
+    let literal_len = parts
+        .iter()
+        .take_while(|part| !part.contains(['*', '?', '[', '{']))
+        .count();
+    (literal_len > 0 && literal_len < parts.len()).then(|| parts[..literal_len].join("/"))
+}
+
+/// Returns true for an `echo` operand that reads as a path glob (`src/*.rs`,
+/// `*.md`) rather than a message. Only globbing at the very end of a word
+/// (`done?`, `ready?!`) is treated as punctuation.
+fn is_listing_glob(arg: &str) -> bool {
+    let pathish = arg.chars().all(|c| {
+        c.is_alphanumeric() || matches!(c, '.' | '_' | '-' | '/' | '~' | '*' | '?' | '[' | ']')
     });
-    parts
-        .next()
-        .map(str::to_string)
-        .unwrap_or_else(|| trimmed.to_string())
+    let stem = arg.trim_end_matches(['*', '?', '!', '.']);
+    pathish
+        && arg.contains(['*', '?'])
+        && (stem.is_empty() || arg.contains('/') || stem.contains(['*', '?']))
+}
+
+/// Like [`short_display_path`], but a glob path (`src/**/*.rs`) shows the
+/// directory it matches in instead of the file pattern.
+fn glob_display_path(path: &str) -> String {
+    match glob_literal_dir(path) {
+        Some(dir) => short_display_path(&dir),
+        None => short_display_path(path),
+    }
 }
 
 // Skip values consumed by specific flags and ignore --flag=value style arguments.
@@ -1589,6 +3714,10 @@ fn positional_operands<'a>(args: &'a [String], flags_with_vals: &[&str]) -> Vec<
             skip_next = false;
             continue;
         }
+        // A lone `-` names stdin rather than a file, even after `--`.
+        if arg == "-" {
+            continue;
+        }
         if after_double_dash {
             out.push(arg);
             continue;
@@ -1622,7 +3751,10 @@ fn parse_grep_like(main_cmd: &[String], args: &[String]) -> ParsedCommand {
     let mut iter = args_no_connector.iter().peekable();
     while let Some(arg) = iter.next() {
         if after_double_dash {
-            operands.push(arg);
+            // A lone `-` in file position searches stdin rather than a path.
+            if arg != "-" || (pattern.is_none() && operands.is_empty()) {
+                operands.push(arg);
+            }
             continue;
         }
         if arg == "--" {
@@ -1638,6 +3770,12 @@ fn parse_grep_like(main_cmd: &[String], args: &[String]) -> ParsedCommand {
                 }
                 continue;
             }
+            _ if attached_regexp(arg).is_some() => {
+                if pattern.is_none() {
+                    pattern = attached_regexp(arg).map(ToString::to_string);
+                }
+                continue;
+            }
             "-f" | "--file" => {
                 if let Some(pat_file) = iter.next()
                     && pattern.is_none()
@@ -1647,10 +3785,17 @@ fn parse_grep_like(main_cmd: &[String], args: &[String]) -> ParsedCommand {
                 continue;
             }
             "-m" | "--max-count" | "-C" | "--context" | "-A" | "--after-context" | "-B"
-            | "--before-context" => {
+            | "--before-context" | "--include" | "--exclude" | "--include-dir"
+            | "--exclude-dir" | "--exclude-from" => {
                 iter.next();
                 continue;
             }
+            // grep's `--color` value is optional, so only consume a following
+            // word when it is one of the values grep accepts.
+            "--color" | "--colour" => {
+                iter.next_if(|when| matches!(when.as_str(), "always" | "never" | "auto"));
+                continue;
+            }
             _ => {}
         }
         if arg.starts_with('-') {
@@ -1661,9 +3806,12 @@ fn parse_grep_like(main_cmd: &[String], args: &[String]) -> ParsedCommand {
     // Do not shorten the query: grep patterns may legitimately contain slashes
     // and should be preserved verbatim. Only paths should be shortened.
     let has_pattern = pattern.is_some();
-    let query = pattern.or_else(|| operands.first().cloned().map(String::from));
+    // An empty pattern matches every line, so there is no search term to show.
+    let query = pattern
+        .or_else(|| operands.first().cloned().map(String::from))
+        .filter(|query| !query.is_empty());
     let path_index = if has_pattern { 0 } else { 1 };
-    let path = operands.get(path_index).map(|s| short_display_path(s));
+    let path = operands.get(path_index).map(|s| glob_display_path(s));
     ParsedCommand::Search {
         cmd: shlex_join(main_cmd),
         query,
@@ -1671,6 +3819,80 @@ fn parse_grep_like(main_cmd: &[String], args: &[String]) -> ParsedCommand {
     }
 }
 
+/// Returns the pattern of an attached `-ePAT` or `--regexp=PAT` argument.
+fn attached_regexp(arg: &str) -> Option<&str> {
+    arg.strip_prefix("--regexp=").or_else(|| {
+        arg.strip_prefix("-e")
+            .filter(|pattern| !pattern.is_empty() && !arg.starts_with("--"))
+    })
+}
+
+/// Returns the first input file given to the text filter `cmd` (`sort`,
+/// `uniq`, or `cut`), skipping option values such as `sort -k 2`. Returns
+/// `None` when `sort` writes its result to a file with `-o`/`--output`.
+fn text_filter_file_operand(cmd: &str, args: &[String]) -> Option<String> {
+    if cmd == "sort" && sort_writes_output_file(args) {
+        return None;
+    }
+    let flags_with_vals: &[&str] = match cmd {
+        "sort" => &[
+            "-k",
+            "--key",
+            "-t",
+            "--field-separator",
+            "-o",
+            "--output",
+            "-S",
+            "--buffer-size",
+            "-T",
+            "--temporary-directory",
+            "--parallel",
+            "--batch-size",
+            "--compress-program",
+            "--files0-from",
+        ],
+        "uniq" => &[
+            "-f",
+            "--skip-fields",
+            "-s",
+            "--skip-chars",
+            "-w",
+            "--check-chars",
+        ],
+        "cut" => &[
+            "-b",
+            "--bytes",
+            "-c",
+            "--characters",
+            "-d",
+            "--delimiter",
+            "-f",
+            "--fields",
+            "--output-delimiter",
+        ],
+        _ => &[],
+    };
+    first_non_flag_operand(trim_at_connector(args).as_slice(), flags_with_vals)
+}
+
+/// Whether `sort` was given `-o FILE`, `-oFILE`, `--output FILE`, or
+/// `--output=FILE`, including `-o` inside a cluster such as `-no`.
+fn sort_writes_output_file(args: &[String]) -> bool {
+    trim_at_connector(args).iter().any(|arg| {
+        if arg == "--output" || arg.starts_with("--output=") {
+            return true;
+        }
+        let Some(cluster) = arg.strip_prefix('-').filter(|rest| !rest.starts_with('-')) else {
+            return false;
+        };
+        // Stop at the first short flag whose value is attached to it.
+        cluster
+            .chars()
+            .take_while(|flag| !matches!(flag, 'k' | 't' | 'S' | 'T'))
+            .any(|flag| flag == 'o')
+    })
+}
+
 fn awk_data_file_operand(args: &[String]) -> Option<String> {
     if args.is_empty() {
         return None;
@@ -1785,6 +4007,8 @@ fn parse_fd_query_and_path(tail: &[String]) -> (Option<String>, Option<String>)
                 (Some((*one).clone()), None)
             }
         }
+        // An empty pattern matches everything, which is just a listing.
+        [q, p, ..] if q.is_empty() => (None, Some(short_display_path(p))),
         [q, p, ..] => (Some((*q).clone()), Some(short_display_path(p))),
         _ => (None, None),
     }
@@ -1792,12 +4016,26 @@ fn parse_fd_query_and_path(tail: &[String]) -> (Option<String>, Option<String>)
 
 fn parse_find_query_and_path(tail: &[String]) -> (Option<String>, Option<String>) {
     let args_no_connector = trim_at_connector(tail);
-    // First positional argument (excluding common unary operators) is the root path
+    // First positional argument (excluding common unary operators and the values
+    // consumed by options such as `-maxdepth 2`) is the root path.
     let mut path: Option<String> = None;
-    for a in &args_no_connector {
-        if !a.starts_with('-') && *a != "!" && *a != "(" && *a != ")" {
-            path = Some(short_display_path(a));
-            break;
+    let mut args = args_no_connector.iter().peekable();
+    while let Some(a) = args.next() {
+        match a.as_str() {
+            "-maxdepth" | "-mindepth" | "-name" | "-iname" | "-path" | "-ipath" | "-regex"
+            | "-iregex" | "-type" | "-newer" | "-size" | "-mtime" | "-mmin" | "-user"
+            | "-group" | "-perm" => {
+                args.next();
+            }
+            // BSD `find` accepts `-depth n`; GNU `-depth` is a plain flag.
+            "-depth" => {
+                args.next_if(|n| n.chars().all(|c| c.is_ascii_digit()));
+            }
+            _ if !a.starts_with('-') && *a != "!" && *a != "(" && *a != ")" => {
+                path = Some(short_display_path(a));
+                break;
+            }
+            _ => {}
         }
     }
     // Extract a common name/path/regex pattern if present
@@ -1807,7 +4045,7 @@ fn parse_find_query_and_path(tail: &[String]) -> (Option<String>, Option<String>
         let a = &args_no_connector[i];
         if a == "-name" || a == "-iname" || a == "-path" || a == "-regex" {
             if i + 1 < args_no_connector.len() {
-                query = Some(args_no_connector[i + 1].clone());
+                query = Some(args_no_connector[i + 1].clone()).filter(|q| !q.is_empty());
             }
             break;
         }
@@ -1822,133 +4060,231 @@ fn parse_shell_lc_commands(original: &[String]) -> Option<Vec<ParsedCommand>> {
     Some(parse_shell_script(script))
 }
 
-/// Parses command metadata from a Bash-compatible shell script.
-pub fn parse_shell_script(script: &str) -> Vec<ParsedCommand> {
-    if let Some(tree) = try_parse_shell(script)
-        && let Some(all_commands) = try_parse_word_only_commands_sequence(&tree, script)
-        && !all_commands.is_empty()
-    {
-        let script_tokens = shlex_split(script).unwrap_or_else(|| vec![script.to_string()]);
-        // Strip small formatting helpers (e.g., head/tail/awk/wc/etc) so we
-        // bias toward the primary command when pipelines are present.
-        // First, drop obvious small formatting helpers (e.g., wc/awk/etc).
-        let had_multiple_commands = all_commands.len() > 1;
-        // Commands arrive in source order; drop formatting helpers while preserving it.
-        let filtered_commands = drop_small_formatting_commands(all_commands);
-        if filtered_commands.is_empty() {
-            return vec![ParsedCommand::Unknown {
-                cmd: script.to_string(),
-            }];
-        }
-        // Build parsed commands, tracking `cd` segments to compute effective file paths.
-        let mut commands: Vec<ParsedCommand> = Vec::new();
-        let mut cwd: Option<String> = None;
-        for tokens in filtered_commands.into_iter() {
-            if let Some((head, tail)) = tokens.split_first()
-                && head == "cd"
-            {
-                if let Some(dir) = cd_target(tail) {
-                    cwd = Some(match &cwd {
-                        Some(base) => join_paths(base, &dir),
-                        None => dir.clone(),
-                    });
-                }
-                continue;
+/// Parses command metadata from a Bash-compatible shell script.
+///
+/// Returns one raw summary per recognized command, without the deduplication,
+/// `Unknown` collapsing, or cap that [`parse_bash_script`] applies.
+pub fn parse_shell_script(script: &str) -> Vec<ParsedCommand> {
+    parse_shell_script_at_depth(script, /*shell_depth*/ 0)
+}
+
+/// Nested `bash -lc` invocations are unwrapped at most this many times.
+const MAX_NESTED_SHELL_DEPTH: usize = 4;
+
+fn parse_shell_script_at_depth(script: &str, shell_depth: usize) -> Vec<ParsedCommand> {
+    // Most scripts are a single plain command; those skip the tree-sitter parse.
+    if let Some(words) = simple_script_words(script) {
+        return summarize_shell_commands(script, vec![words], shell_depth);
+    }
+    let tree = try_parse_shell(script);
+    if let Some(tree) = &tree
+        && let Some(stripped) = strip_shell_comments(tree, script)
+    {
+        return parse_shell_script_at_depth(&stripped, shell_depth);
+    }
+    // A script wrapped in a single `( ... )` subshell is summarized by its body.
+    // This is display-only: the safety checks still reject subshells.
+    if let Some(tree) = &tree
+        && !tree.root_node().has_error()
+        && tree.root_node().named_child_count() == 1
+        && let Some(subshell) = tree.root_node().named_child(0)
+        && subshell.kind() == "subshell"
+        && let Some(body) = script
+            .get(subshell.byte_range())
+            .and_then(|text| text.strip_prefix('('))
+            .and_then(|text| text.strip_suffix(')'))
+    {
+        return parse_shell_script_at_depth(body.trim(), shell_depth);
+    }
+    if let Some(tree) = &tree
+        && let Some(all_commands) = try_parse_word_only_commands_sequence(tree, script)
+        && !all_commands.is_empty()
+    {
+        return summarize_shell_commands(script, all_commands, shell_depth);
+    }
+    vec![ParsedCommand::Unknown {
+        cmd: script.to_string(),
+    }]
+}
+
+/// Words that the shell grammar treats specially in command position, so a
+/// script starting with one never takes the simple-command fast path.
+const SHELL_KEYWORDS: &[&str] = &[
+    "case", "coproc", "declare", "do", "done", "elif", "else", "esac", "export", "fi", "for",
+    "function", "if", "in", "local", "readonly", "select", "then", "time", "typeset", "unset",
+    "unsetenv", "until", "while",
+];
+
+/// Splits `script` on spaces when it is a single command built only from
+/// characters the shell never interprets, giving the same words the
+/// tree-sitter parse would. Returns `None` for anything else.
+fn simple_script_words(script: &str) -> Option<Vec<String>> {
+    let is_plain = |c: char| {
+        c.is_ascii_alphanumeric()
+            || matches!(c, ' ' | '-' | '_' | '.' | '/' | ',' | ':' | '+' | '@')
+    };
+    if !script.chars().all(is_plain) {
+        return None;
+    }
+    let words: Vec<String> = script.split_whitespace().map(str::to_string).collect();
+    let first = words.first()?;
+    if SHELL_KEYWORDS.contains(&first.as_str()) {
+        return None;
+    }
+    Some(words)
+}
+
+/// Summarizes the word-only commands parsed out of `script`, in source order.
+fn summarize_shell_commands(
+    script: &str,
+    all_commands: Vec<Vec<String>>,
+    shell_depth: usize,
+) -> Vec<ParsedCommand> {
+    // A lone command's parsed words already omit anything the parser dropped,
+    // such as a trailing `2>/dev/null`.
+    let script_tokens = match all_commands.as_slice() {
+        [words] => words.clone(),
+        _ => shlex_split(script).unwrap_or_else(|| vec![script.to_string()]),
+    };
+    // Strip small formatting helpers (e.g., head/tail/awk/wc/etc) so we
+    // bias toward the primary command when pipelines are present.
+    // First, drop obvious small formatting helpers (e.g., wc/awk/etc).
+    let had_multiple_commands = all_commands.len() > 1;
+    // Commands arrive in source order; drop formatting helpers while preserving it.
+    let filtered_commands = drop_small_formatting_commands(all_commands);
+    if filtered_commands.is_empty() {
+        return vec![ParsedCommand::Unknown {
+            cmd: script.to_string(),
+        }];
+    }
+    // Build parsed commands, tracking `cd` segments to compute effective file paths.
+    let mut commands: Vec<ParsedCommand> = Vec::new();
+    let mut cwd: Option<String> = None;
+    for tokens in filtered_commands.into_iter() {
+        if let Some((head, tail)) = tokens.split_first()
+            && head == "cd"
+        {
+            if let Some(dir) = cd_target(tail) {
+                cwd = Some(match &cwd {
+                    Some(base) => join_paths(base, &dir),
+                    None => dir.clone(),
+                });
             }
-            let parsed = summarize_main_tokens(&tokens);
-            let parsed = match parsed {
-                ParsedCommand::Read { cmd, name, path } => {
-                    if let Some(base) = &cwd {
-                        let full = join_paths(base, &path.to_string_lossy());
-                        ParsedCommand::Read {
-                            cmd,
-                            name,
-                            path: PathBuf::from(full),
-                        }
-                    } else {
-                        ParsedCommand::Read { cmd, name, path }
-                    }
-                }
-                other => other,
-            };
-            commands.push(parsed);
+            continue;
         }
+        for parsed in summarize_command_segment(&tokens, shell_depth) {
+            commands.push(resolve_against_cwd(parsed, cwd.as_deref()));
+        }
+    }
 
-        if commands.len() > 1 {
-            commands.retain(|pc| !matches!(pc, ParsedCommand::Unknown { cmd } if cmd == "true"));
-            // Apply the same simplifications used for non-bash parsing, e.g., drop leading `cd`.
-            while let Some(next) = simplify_once(&commands) {
-                commands = next;
-            }
+    if commands.len() > 1 {
+        commands.retain(|pc| !matches!(pc, ParsedCommand::Unknown { cmd } if cmd == "true"));
+        // Apply the same simplifications used for non-bash parsing, e.g., drop leading `cd`.
+        while let Some(next) = simplify_once(&commands) {
+            commands = next;
         }
-        if commands.len() == 1 {
-            // If we reduced to a single command, attribute the full original script
-            // for clearer UX in file-reading and listing scenarios, or when there were
-            // no connectors in the original script. For pipeline commands (e.g.
-            // `rg --files | sed -n`), keep only the primary command.
-            let had_connectors = had_multiple_commands
-                || script_tokens
-                    .iter()
-                    .any(|t| t == "|" || t == "&&" || t == "||" || t == ";");
-            commands = commands
-                .into_iter()
-                .map(|pc| match pc {
-                    ParsedCommand::Read { name, cmd, path } => {
-                        if had_connectors {
-                            let has_pipe = script_tokens.iter().any(|t| t == "|");
-                            let has_sed_n = script_tokens.windows(2).any(|w| {
-                                w.first().map(String::as_str) == Some("sed")
-                                    && w.get(1).map(String::as_str) == Some("-n")
-                            });
-                            if has_pipe && has_sed_n {
-                                ParsedCommand::Read {
-                                    cmd: script.to_string(),
-                                    name,
-                                    path,
-                                }
-                            } else {
-                                ParsedCommand::Read { cmd, name, path }
-                            }
-                        } else {
+    }
+    if commands.len() == 1 {
+        // If we reduced to a single command, attribute the full original script
+        // for clearer UX in file-reading and listing scenarios, or when there were
+        // no connectors in the original script. For pipeline commands (e.g.
+        // `rg --files | sed -n`), keep only the primary command.
+        let had_connectors = had_multiple_commands
+            || script_tokens
+                .iter()
+                .any(|t| t == "|" || t == "&&" || t == "||" || t == ";");
+        commands = commands
+            .into_iter()
+            .map(|pc| match pc {
+                ParsedCommand::Read { name, cmd, path } => {
+                    if had_connectors {
+                        let has_pipe = script_tokens.iter().any(|t| t == "|");
+                        let has_sed_n = script_tokens.windows(2).any(|w| {
+                            w.first().map(String::as_str) == Some("sed")
+                                && w.get(1).map(String::as_str) == Some("-n")
+                        });
+                        if has_pipe && has_sed_n {
                             ParsedCommand::Read {
-                                cmd: shlex_join(&script_tokens),
+                                cmd: script.to_string(),
                                 name,
                                 path,
                             }
+                        } else {
+                            ParsedCommand::Read { cmd, name, path }
+                        }
+                    } else {
+                        ParsedCommand::Read {
+                            cmd: shlex_join(&script_tokens),
+                            name,
+                            path,
                         }
                     }
-                    ParsedCommand::ListFiles { path, cmd, .. } => {
-                        if had_connectors {
-                            ParsedCommand::ListFiles { cmd, path }
-                        } else {
-                            ParsedCommand::ListFiles {
-                                cmd: shlex_join(&script_tokens),
-                                path,
-                            }
+                }
+                ParsedCommand::ListFiles { path, cmd, .. } => {
+                    if had_connectors {
+                        ParsedCommand::ListFiles { cmd, path }
+                    } else {
+                        ParsedCommand::ListFiles {
+                            cmd: shlex_join(&script_tokens),
+                            path,
                         }
                     }
-                    ParsedCommand::Search {
-                        query, path, cmd, ..
-                    } => {
-                        if had_connectors {
-                            ParsedCommand::Search { cmd, query, path }
-                        } else {
-                            ParsedCommand::Search {
-                                cmd: shlex_join(&script_tokens),
-                                query,
-                                path,
-                            }
+                }
+                ParsedCommand::Search {
+                    query, path, cmd, ..
+                } => {
+                    if had_connectors {
+                        ParsedCommand::Search { cmd, query, path }
+                    } else {
+                        ParsedCommand::Search {
+                            cmd: shlex_join(&script_tokens),
+                            query,
+                            path,
                         }
                     }
-                    other => other,
-                })
-                .collect();
+                }
+                other => other,
+            })
+            .collect();
+    }
+    commands
+}
+
+/// Removes `# ...` comments from `script`, along with a connector left dangling
+/// at the end (e.g. `ls && # note`), so the remaining commands can be
+/// summarized. Returns `None` when the script contains no comments.
+fn strip_shell_comments(tree: &Tree, script: &str) -> Option<String> {
+    let mut comments = Vec::new();
+    let mut cursor = tree.walk();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "comment" {
+            comments.push(node.byte_range());
+            continue;
         }
-        return commands;
+        stack.extend(node.children(&mut cursor));
     }
-    vec![ParsedCommand::Unknown {
-        cmd: script.to_string(),
-    }]
+    if comments.is_empty() {
+        return None;
+    }
+    comments.sort_by_key(|range| range.start);
+
+    let mut stripped = String::with_capacity(script.len());
+    let mut last_end = 0;
+    for range in comments {
+        stripped.push_str(&script[last_end..range.start]);
+        last_end = range.end;
+    }
+    stripped.push_str(&script[last_end..]);
+
+    let mut trimmed = stripped.trim_end();
+    while let Some(rest) = ["&&", "||", "|", ";"]
+        .iter()
+        .find_map(|connector| trimmed.strip_suffix(connector))
+    {
+        trimmed = rest.trim_end();
+    }
+    Some(trimmed.to_string())
 }
 
 /// Return true if this looks like a small formatting helper in a pipeline.
@@ -1962,8 +4298,14 @@ fn is_small_formatting_command(tokens: &[String]) -> bool {
     match cmd {
         // Always formatting; typically used in pipes.
         // `nl` is special-cased below to allow `nl <file>` to be treated as a read command.
-        "wc" | "tr" | "cut" | "sort" | "uniq" | "tee" | "column" | "yes" | "printf" => true,
+        "wc" | "tr" | "tee" | "column" | "yes" | "printf" => true,
+        // Text filters only format a pipeline; `sort data.txt` is still a read
+        // and `sort -o out` writes a file.
+        "sort" if sort_writes_output_file(&tokens[1..]) => false,
+        "cut" | "sort" | "uniq" => text_filter_file_operand(cmd, &tokens[1..]).is_none(),
         "xargs" => !is_mutating_xargs_command(tokens),
+        // Pagers only display their input; `less <file>` is still a read.
+        "less" | "more" => tokens[1..].iter().all(|arg| arg.starts_with('-')),
         "awk" => awk_data_file_operand(&tokens[1..]).is_none(),
         "head" => {
             // Treat as formatting when no explicit file operand is present.
@@ -2076,8 +4418,343 @@ fn drop_small_formatting_commands(mut commands: Vec<Vec<String>>) -> Vec<Vec<Str
     commands
 }
 
+/// Summarizes a single command segment. Most commands map to exactly one
+/// summary; commands that touch several files (e.g. `sha256sum a b`,
+/// `rm a b`) expand into one summary per file operand, and nested
+/// `bash -lc '...'` invocations are flattened into the summaries of their
+/// inner script.
+fn summarize_command_segment(main_cmd: &[String], shell_depth: usize) -> Vec<ParsedCommand> {
+    if let Some((_, script)) = extract_bash_command(main_cmd) {
+        if shell_depth >= MAX_NESTED_SHELL_DEPTH {
+            return vec![ParsedCommand::Unknown {
+                cmd: shlex_join(main_cmd),
+            }];
+        }
+        return parse_shell_script_at_depth(script, shell_depth + 1);
+    }
+    match main_cmd
+        .split_first()
+        .map(|(head, tail)| (program_name(head).to_string(), tail))
+    {
+        Some((head, tail))
+            if matches!(
+                head.as_str(),
+                "md5sum" | "sha1sum" | "sha256sum" | "sha512sum" | "b2sum" | "cksum"
+            ) =>
+        {
+            let files = positional_operands(tail, &["-a", "--algorithm", "-l", "--length"]);
+            if files.is_empty() {
+                return vec![ParsedCommand::Unknown {
+                    cmd: shlex_join(main_cmd),
+                }];
+            }
+            files
+                .into_iter()
+                .map(|path| ParsedCommand::Read {
+                    cmd: shlex_join(main_cmd),
+                    name: short_display_path(path),
+                    path: PathBuf::from(path),
+                })
+                .collect()
+        }
+        // `diff` compares two operands: files are read, `-r` walks both directories.
+        Some((head, tail)) if head == "diff" => {
+            let operands = positional_operands(
+                tail,
+                &[
+                    "-x",
+                    "--exclude",
+                    "-X",
+                    "--exclude-from",
+                    "-I",
+                    "--ignore-matching-lines",
+                    "-S",
+                    "--starting-file",
+                    "--label",
+                ],
+            );
+            let recursive = tail.iter().take_while(|arg| *arg != "--").any(|arg| {
+                arg == "--recursive"
+                    || (arg.starts_with('-') && !arg.starts_with("--") && arg.contains('r'))
+            });
+            if operands.len() != 2 {
+                return vec![ParsedCommand::Unknown {
+                    cmd: shlex_join(main_cmd),
+                }];
+            }
+            operands
+                .into_iter()
+                .map(|path| {
+                    if recursive {
+                        ParsedCommand::ListFiles {
+                            cmd: shlex_join(main_cmd),
+                            path: Some(short_display_path(path)),
+                        }
+                    } else {
+                        ParsedCommand::Read {
+                            cmd: shlex_join(main_cmd),
+                            name: short_display_path(path),
+                            path: PathBuf::from(path),
+                        }
+                    }
+                })
+                .collect()
+        }
+        // `git diff -- a.rs b.rs` shows the changes to each pathspec.
+        Some((head, tail)) if head == "git" && !git_diff_pathspecs(tail).is_empty() => {
+            git_diff_pathspecs(tail)
+                .into_iter()
+                .map(|path| ParsedCommand::Read {
+                    cmd: shlex_join(main_cmd),
+                    name: short_display_path(path),
+                    path: PathBuf::from(path),
+                })
+                .collect()
+        }
+        // `command`/`builtin`/`exec` run the command that follows them.
+        Some((head, tail)) if matches!(head.as_str(), "command" | "builtin" | "exec") => {
+            match prefixed_command_start(&head, tail) {
+                Some(start) if start < tail.len() => {
+                    summarize_command_segment(&tail[start..], shell_depth)
+                }
+                _ => vec![ParsedCommand::Unknown {
+                    cmd: shlex_join(main_cmd),
+                }],
+            }
+        }
+        // `watch` reruns its command until interrupted; summarize what it runs.
+        Some((head, tail)) if head == "watch" => {
+            let Some(start) = watch_command_start(tail) else {
+                return vec![ParsedCommand::Unknown {
+                    cmd: shlex_join(main_cmd),
+                }];
+            };
+            match &tail[start..] {
+                // A single argument is a script that watch hands to `sh -c`.
+                [_] if shell_depth >= MAX_NESTED_SHELL_DEPTH => vec![ParsedCommand::Unknown {
+                    cmd: shlex_join(main_cmd),
+                }],
+                [script] => parse_shell_script_at_depth(script, shell_depth + 1),
+                inner => summarize_command_segment(inner, shell_depth),
+            }
+        }
+        Some((head, tail)) if matches!(head.as_str(), "head" | "tail") => {
+            // `head -q -n 10 a.txt b.txt` reads each file. Anything beyond the count
+            // and header options (e.g. `tail -f`) keeps the single-command summary.
+            let mut files = Vec::new();
+            let mut args = tail.iter();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "-q" | "-v" | "--quiet" | "--silent" | "--verbose" | "-" => {}
+                    "--" => {
+                        files.extend(args.by_ref().filter(|arg| *arg != "-"));
+                    }
+                    "-n" | "-c" | "--lines" | "--bytes" => {
+                        if args.next().is_none() {
+                            return vec![summarize_main_tokens(main_cmd)];
+                        }
+                    }
+                    _ if arg.starts_with("--lines=") || arg.starts_with("--bytes=") => {}
+                    _ if (arg.starts_with("-n") || arg.starts_with("-c")) && arg.len() > 2 => {}
+                    _ if arg.starts_with('-') => return vec![summarize_main_tokens(main_cmd)],
+                    _ => files.push(arg),
+                }
+            }
+            if files.is_empty() {
+                return vec![summarize_main_tokens(main_cmd)];
+            }
+            files
+                .into_iter()
+                .map(|path| ParsedCommand::Read {
+                    cmd: shlex_join(main_cmd),
+                    name: short_display_path(path),
+                    path: PathBuf::from(path),
+                })
+                .collect()
+        }
+        Some((head, tail)) if matches!(head.as_str(), "rm" | "rmdir" | "shred") => {
+            let targets = positional_operands(tail, &["-n", "--iterations", "-s", "--size"]);
+            if targets.is_empty() {
+                return vec![ParsedCommand::Unknown {
+                    cmd: shlex_join(main_cmd),
+                }];
+            }
+            targets
+                .into_iter()
+                .map(|path| ParsedCommand::Delete {
+                    cmd: shlex_join(main_cmd),
+                    path: path.clone(),
+                })
+                .collect()
+        }
+        Some((head, tail)) if head == "mv" => {
+            let mut sources =
+                positional_operands(tail, &["-S", "--suffix", "-t", "--target-directory"]);
+            let target_dir = tail
+                .iter()
+                .enumerate()
+                .find_map(|(idx, arg)| match arg.as_str() {
+                    "-t" | "--target-directory" => tail.get(idx + 1).cloned(),
+                    _ => arg
+                        .strip_prefix("--target-directory=")
+                        .map(ToString::to_string),
+                });
+            let destination = target_dir.or_else(|| sources.pop().cloned());
+            match destination {
+                Some(to) if !sources.is_empty() => sources
+                    .into_iter()
+                    .map(|from| ParsedCommand::Move {
+                        cmd: shlex_join(main_cmd),
+                        from: from.clone(),
+                        to: to.clone(),
+                    })
+                    .collect(),
+                _ => vec![ParsedCommand::Unknown {
+                    cmd: shlex_join(main_cmd),
+                }],
+            }
+        }
+        _ => vec![summarize_main_tokens(main_cmd)],
+    }
+}
+
+/// Returns the pathspecs after `--` in `git diff`/`git show`, unless the output
+/// is a stat or name listing rather than the changes themselves.
+fn git_diff_pathspecs(args: &[String]) -> Vec<&String> {
+    let Some((subcmd, rest)) = git_subcommand_args(args).split_first() else {
+        return Vec::new();
+    };
+    let lists_names = rest.iter().any(|arg| {
+        matches!(
+            arg.as_str(),
+            "--stat" | "--name-only" | "--name-status" | "--numstat" | "--shortstat"
+        ) || arg.starts_with("--stat=")
+    });
+    if !matches!(subcmd.as_str(), "diff" | "show") || lists_names {
+        return Vec::new();
+    }
+    rest.iter()
+        .position(|arg| arg == "--")
+        .map(|idx| rest[idx + 1..].iter().collect())
+        .unwrap_or_default()
+}
+
+/// Skips git's global options (`-C <dir>`, `-c <key=val>`, `--no-pager`, ...)
+/// so `args` starts at the subcommand.
+fn git_subcommand_args(args: &[String]) -> &[String] {
+    let mut idx = 0;
+    while let Some(arg) = args.get(idx) {
+        match arg.as_str() {
+            "-C" | "-c" | "--config-env" | "--exec-path" | "--git-dir" | "--namespace"
+            | "--super-prefix" | "--work-tree" => idx += 2,
+            flag if flag.starts_with('-') && flag != "--" => idx += 1,
+            _ => break,
+        }
+    }
+    args.get(idx..).unwrap_or_default()
+}
+
+/// Returns the index in `args` where the command run by the `command`,
+/// `builtin`, or `exec` prefix `head` begins. `command -v`/`-V` only describe
+/// a name, so they have no wrapped command.
+fn prefixed_command_start(head: &str, args: &[String]) -> Option<usize> {
+    let mut idx = 0;
+    while let Some(arg) = args.get(idx) {
+        match (head, arg.as_str()) {
+            ("command", "-p") | ("exec", "-c" | "-l" | "-cl" | "-lc") => idx += 1,
+            ("exec", "-a") => idx += 2,
+            ("command" | "exec", "--") => return Some(idx + 1),
+            ("command", "-v" | "-V") => return None,
+            (_, flag) if flag.starts_with('-') && head != "builtin" => return None,
+            _ => break,
+        }
+    }
+    Some(idx)
+}
+
+/// Returns the index in `args` (the arguments to `watch`) where the watched
+/// command begins, skipping `watch`'s own options.
+fn watch_command_start(args: &[String]) -> Option<usize> {
+    let mut idx = 0;
+    while let Some(arg) = args.get(idx) {
+        match arg.as_str() {
+            "--" => return (idx + 1 < args.len()).then_some(idx + 1),
+            "-n" | "--interval" | "-q" | "--equexit" => idx += 2,
+            _ if arg.starts_with('-') => idx += 1,
+            _ => return Some(idx),
+        }
+    }
+    None
+}
+
+/// Tools whose summaries are the same whether they are invoked by bare name,
+/// through a path such as `/usr/bin/rg` or `./bin/grep`, or as `rg.exe`.
+const PATH_QUALIFIED_PROGRAMS: &[&str] = &[
+    "ack",
+    "ag",
+    "awk",
+    "b2sum",
+    "bat",
+    "batcat",
+    "cat",
+    "cksum",
+    "df",
+    "diff",
+    "du",
+    "egrep",
+    "eza",
+    "exa",
+    "fd",
+    "fgrep",
+    "find",
+    "git",
+    "grep",
+    "head",
+    "jq",
+    "less",
+    "ls",
+    "md5sum",
+    "more",
+    "mv",
+    "nl",
+    "rg",
+    "rm",
+    "rmdir",
+    "sed",
+    "sha1sum",
+    "sha256sum",
+    "sha512sum",
+    "shred",
+    "tail",
+    "tree",
+    "watch",
+];
+
+/// Returns the program name used to pick a summary for `head`, reducing a
+/// path-qualified or `.exe`-suffixed invocation of a known tool to its bare
+/// name.
+fn program_name(head: &str) -> &str {
+    let base = head.rsplit(['/', '\\']).next().unwrap_or(head);
+    let base = match base
+        .len()
+        .checked_sub(4)
+        .and_then(|idx| base.split_at_checked(idx))
+    {
+        Some((stem, suffix)) if suffix.eq_ignore_ascii_case(".exe") => stem,
+        _ => base,
+    };
+    if PATH_QUALIFIED_PROGRAMS.contains(&base) {
+        base
+    } else {
+        head
+    }
+}
+
 fn summarize_main_tokens(main_cmd: &[String]) -> ParsedCommand {
-    match main_cmd.split_first() {
+    match main_cmd
+        .split_first()
+        .map(|(head, tail)| (program_name(head).to_string(), tail))
+    {
         Some((head, tail)) if matches!(head.as_str(), "ls" | "eza" | "exa") => {
             let flags_with_vals: &[&str] = match head.as_str() {
                 "ls" => &[
@@ -2088,6 +4765,8 @@ fn summarize_main_tokens(main_cmd: &[String]) -> ParsedCommand {
                     "--time-style",
                     "--color",
                     "--quoting-style",
+                    "--sort",
+                    "--time",
                 ],
                 "eza" | "exa" => &[
                     "-I",
@@ -2106,6 +4785,17 @@ fn summarize_main_tokens(main_cmd: &[String]) -> ParsedCommand {
                 path,
             }
         }
+        // `echo src/*.rs` lets the shell expand the glob: a quick directory listing.
+        Some((head, tail))
+            if head == "echo"
+                && !tail.is_empty()
+                && tail.iter().all(|arg| is_listing_glob(arg)) =>
+        {
+            ParsedCommand::ListFiles {
+                cmd: shlex_join(main_cmd),
+                path: tail.first().map(|glob| glob_display_path(glob)),
+            }
+        }
         Some((head, tail)) if head == "tree" => {
             let path = first_non_flag_operand(
                 tail,
@@ -2135,6 +4825,18 @@ fn summarize_main_tokens(main_cmd: &[String]) -> ParsedCommand {
                 path,
             }
         }
+        // `df` reports on the filesystem holding each operand.
+        Some((head, tail)) if head == "df" => {
+            let path = first_non_flag_operand(
+                tail,
+                &["-B", "--block-size", "-t", "--type", "-x", "--exclude-type"],
+            )
+            .map(|p| short_display_path(&p));
+            ParsedCommand::ListFiles {
+                cmd: shlex_join(main_cmd),
+                path,
+            }
+        }
         Some((head, tail)) if head == "rg" || head == "rga" || head == "ripgrep-all" => {
             let args_no_connector = trim_at_connector(tail);
             let has_files_flag = args_no_connector.iter().any(|a| a == "--files");
@@ -2155,21 +4857,74 @@ fn summarize_main_tokens(main_cmd: &[String]) -> ParsedCommand {
                     "-C",
                     "--context",
                     "--max-depth",
+                    "--color",
+                    "--colors",
+                    "-e",
+                    "--regexp",
+                    "-f",
+                    "--file",
+                    "--pre",
+                    "--pre-glob",
+                    "--hostname-bin",
+                    "--files-from",
                 ],
             );
+            // Boolean flags such as `-U`/`--multiline` are dropped here. A multiline
+            // pattern keeps its `\n` escapes or literal newlines verbatim as the query.
             let non_flags: Vec<&String> = candidates
                 .into_iter()
                 .filter(|p| !p.starts_with('-'))
                 .collect();
+            // A pattern passed via `-e`/`--regexp` may itself start with `-`. A bare
+            // dash-leading query (`rg '-v' src`) cannot be told apart from a flag once
+            // the shell strips its quotes, so that form is still treated as a flag.
+            let explicit_patterns: Vec<String> = args_no_connector
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, arg)| match arg.as_str() {
+                    "-e" | "--regexp" => args_no_connector.get(idx + 1).cloned(),
+                    _ => attached_regexp(arg).map(ToString::to_string),
+                })
+                .collect();
+            // Several `-e` patterns match when any of them does, so show them as
+            // alternatives: `rg -e foo -e bar` searches for `foo|bar`.
+            let explicit_query =
+                (!explicit_patterns.is_empty()).then(|| explicit_patterns.join("|"));
             if has_files_flag {
-                let path = non_flags.first().map(|s| short_display_path(s));
+                // Without an explicit directory, the literal prefix of an inclusion glob
+                // (`-g 'src/**'`) hints at where the listing happens. Exclusion globs
+                // (`-g '!target'`) name what is skipped, so they never become the path.
+                let path = non_flags
+                    .first()
+                    .map(|s| short_display_path(s))
+                    .or_else(|| {
+                        args_no_connector
+                            .windows(2)
+                            .filter(|pair| matches!(pair[0].as_str(), "-g" | "--glob"))
+                            .map(|pair| pair[1].as_str())
+                            .find(|glob| !glob.starts_with('!'))
+                            .and_then(glob_literal_dir)
+                            .map(|dir| short_display_path(&dir))
+                    });
                 ParsedCommand::ListFiles {
                     cmd: shlex_join(main_cmd),
                     path,
                 }
             } else {
-                let query = non_flags.first().cloned().map(String::from);
-                let path = non_flags.get(1).map(|s| short_display_path(s));
+                // Patterns read from a file (`-f patterns.txt`) leave only paths behind.
+                let has_pattern_file = args_no_connector
+                    .iter()
+                    .any(|arg| arg == "-f" || arg == "--file" || arg.starts_with("--file="));
+                let (query, path) = match explicit_query {
+                    Some(query) => (Some(query), non_flags.first()),
+                    None if has_pattern_file => (None, non_flags.first()),
+                    None => (
+                        non_flags.first().cloned().map(String::from),
+                        non_flags.get(1),
+                    ),
+                };
+                let query = query.filter(|query| !query.is_empty());
+                let path = path.map(|s| glob_display_path(s));
                 ParsedCommand::Search {
                     cmd: shlex_join(main_cmd),
                     query,
@@ -2177,7 +4932,7 @@ fn summarize_main_tokens(main_cmd: &[String]) -> ParsedCommand {
                 }
             }
         }
-        Some((head, tail)) if head == "git" => match tail.split_first() {
+        Some((head, tail)) if head == "git" => match git_subcommand_args(tail).split_first() {
             Some((subcmd, sub_tail)) if subcmd == "grep" => parse_grep_like(main_cmd, sub_tail),
             Some((subcmd, sub_tail)) if subcmd == "ls-files" => {
                 let path = first_non_flag_operand(
@@ -2190,6 +4945,54 @@ fn summarize_main_tokens(main_cmd: &[String]) -> ParsedCommand {
                     path,
                 }
             }
+            // `git show --stat <rev>` / `--name-only` summarize the files touched by a
+            // commit rather than printing file contents, so present them as a listing.
+            Some((subcmd, sub_tail))
+                if subcmd == "show"
+                    && sub_tail.iter().any(|arg| {
+                        matches!(
+                            arg.as_str(),
+                            "--stat"
+                                | "--name-only"
+                                | "--name-status"
+                                | "--numstat"
+                                | "--shortstat"
+                        ) || arg.starts_with("--stat=")
+                    }) =>
+            {
+                let path = sub_tail
+                    .iter()
+                    .position(|arg| arg == "--")
+                    .and_then(|idx| sub_tail.get(idx + 1))
+                    .map(|p| short_display_path(p));
+                ParsedCommand::ListFiles {
+                    cmd: shlex_join(main_cmd),
+                    path,
+                }
+            }
+            // Status output, long or short, is a list of changed paths.
+            Some((subcmd, sub_tail)) if subcmd == "status" => {
+                let path = first_non_flag_operand(sub_tail, &[]).map(|p| short_display_path(&p));
+                ParsedCommand::ListFiles {
+                    cmd: shlex_join(main_cmd),
+                    path,
+                }
+            }
+            _ => ParsedCommand::Unknown {
+                cmd: shlex_join(main_cmd),
+            },
+        },
+        // `bazel query //src/...` searches the build graph for the target pattern.
+        Some((head, tail)) if head == "bazel" => match tail.split_first() {
+            Some((subcmd, sub_tail))
+                if matches!(subcmd.as_str(), "query" | "cquery" | "aquery") =>
+            {
+                ParsedCommand::Search {
+                    cmd: shlex_join(main_cmd),
+                    query: first_non_flag_operand(sub_tail, &["--output"]),
+                    path: None,
+                }
+            }
             _ => ParsedCommand::Unknown {
                 cmd: shlex_join(main_cmd),
             },
@@ -2246,7 +5049,7 @@ fn summarize_main_tokens(main_cmd: &[String]) -> ParsedCommand {
                 .filter(|p| !p.starts_with('-'))
                 .collect();
             let query = non_flags.first().cloned().map(String::from);
-            let path = non_flags.get(1).map(|s| short_display_path(s));
+            let path = non_flags.get(1).map(|s| glob_display_path(s));
             ParsedCommand::Search {
                 cmd: shlex_join(main_cmd),
                 query,
@@ -2254,6 +5057,8 @@ fn summarize_main_tokens(main_cmd: &[String]) -> ParsedCommand {
             }
         }
         Some((head, tail)) if head == "cat" => {
+            // A glob operand (`cat src/*.rs`) stays a single Read of the pattern; the
+            // path keeps the glob so the summary shows which files are read in bulk.
             if let Some(path) = single_non_flag_operand(tail, &[]) {
                 let name = short_display_path(&path);
                 ParsedCommand::Read {
@@ -2453,9 +5258,60 @@ fn summarize_main_tokens(main_cmd: &[String]) -> ParsedCommand {
                 }
             }
         }
+        Some((head, tail)) if matches!(head.as_str(), "sort" | "uniq" | "cut") => {
+            match text_filter_file_operand(&head, tail) {
+                Some(path) => ParsedCommand::Read {
+                    cmd: shlex_join(main_cmd),
+                    name: short_display_path(&path),
+                    path: PathBuf::from(path),
+                },
+                None => ParsedCommand::Unknown {
+                    cmd: shlex_join(main_cmd),
+                },
+            }
+        }
+        Some((head, tail)) if head == "jq" => {
+            // `--slurpfile`/`--rawfile` read auxiliary files bound to a variable; the
+            // primary input is the first file operand after the filter.
+            let mut operands = Vec::new();
+            let mut filter_from_file = false;
+            let mut args = tail.iter();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--arg" | "--argjson" | "--slurpfile" | "--rawfile" => {
+                        args.nth(1);
+                    }
+                    "-f" | "--from-file" => {
+                        filter_from_file = true;
+                        args.next();
+                    }
+                    "-L" | "--indent" => {
+                        args.next();
+                    }
+                    "--args" | "--jsonargs" => break,
+                    _ if arg.starts_with('-') && arg != "-" => {}
+                    _ => operands.push(arg),
+                }
+            }
+            let skip = if filter_from_file { 0 } else { 1 };
+            if let Some(path) = operands.into_iter().nth(skip).filter(|path| *path != "-") {
+                ParsedCommand::Read {
+                    cmd: shlex_join(main_cmd),
+                    name: short_display_path(path),
+                    path: PathBuf::from(path),
+                }
+            } else {
+                ParsedCommand::Unknown {
+                    cmd: shlex_join(main_cmd),
+                }
+            }
+        }
         Some((head, tail)) if head == "nl" => {
             // Avoid treating option values as paths (e.g., nl -s "  ").
-            let candidates = skip_flag_values(tail, &["-s", "-w", "-v", "-i", "-b"]);
+            let candidates = skip_flag_values(
+                tail,
+                &["-s", "-w", "-v", "-i", "-b", "-n", "-d", "-f", "-h", "-l"],
+            );
             if let Some(p) = candidates.into_iter().find(|p| !p.starts_with('-')) {
                 let path = p.clone();
                 let name = short_display_path(&path);
@@ -2484,7 +5340,7 @@ fn summarize_main_tokens(main_cmd: &[String]) -> ParsedCommand {
                 }
             }
         }
-        Some((head, tail)) if is_python_command(head) => {
+        Some((head, tail)) if is_python_command(&head) => {
             if python_walks_files(tail) {
                 ParsedCommand::ListFiles {
                     cmd: shlex_join(main_cmd),
@@ -2507,6 +5363,11 @@ fn is_abs_like(path: &str) -> bool {
     if std::path::Path::new(path).is_absolute() {
         return true;
     }
+    // Home-relative paths are kept verbatim: `~` is not expanded here, but it
+    // must not be resolved against a preceding `cd` either.
+    if path == "~" || path.starts_with("~/") {
+        return true;
+    }
     let mut chars = path.chars();
     match (chars.next(), chars.next(), chars.next()) {
         // Windows drive path like C:\
@@ -2518,6 +5379,32 @@ fn is_abs_like(path: &str) -> bool {
     false
 }
 
+/// Joins the file paths of a summary with the directory of an earlier `cd`.
+fn resolve_against_cwd(parsed: ParsedCommand, cwd: Option<&str>) -> ParsedCommand {
+    let Some(base) = cwd else {
+        return parsed;
+    };
+    match parsed {
+        ParsedCommand::Read { cmd, name, path } => ParsedCommand::Read {
+            cmd,
+            name,
+            path: PathBuf::from(join_paths(base, &path.to_string_lossy())),
+        },
+        ParsedCommand::Delete { cmd, path } => ParsedCommand::Delete {
+            cmd,
+            path: join_paths(base, &path),
+        },
+        ParsedCommand::Move { cmd, from, to } => ParsedCommand::Move {
+            cmd,
+            from: join_paths(base, &from),
+            to: join_paths(base, &to),
+        },
+        ParsedCommand::ListFiles { .. }
+        | ParsedCommand::Search { .. }
+        | ParsedCommand::Unknown { .. } => parsed,
+    }
+}
+
 fn join_paths(base: &str, rel: &str) -> String {
     if is_abs_like(rel) {
         return rel.to_string();
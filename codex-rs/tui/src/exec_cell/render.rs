@@ -323,7 +323,9 @@ impl ExecCell {
                             };
                             lines.push(("Search", spans));
                         }
-                        ParsedCommand::Unknown { cmd } => {
+                        ParsedCommand::Delete { cmd, .. }
+                        | ParsedCommand::Move { cmd, .. }
+                        | ParsedCommand::Unknown { cmd } => {
                             lines.push(("Run", vec![cmd.clone().into()]));
                         }
                     }
@@ -428,6 +430,39 @@ impl ExecCell {
             ));
         }
 
+        // Deletes and moves get their own line so they stand out from the
+        // command text.
+        let mut destructive_lines: Vec<Line<'static>> = Vec::new();
+        for parsed in &call.parsed {
+            let line = match parsed {
+                ParsedCommand::Delete { path, .. } => {
+                    Line::from(vec!["Delete".red().bold(), " ".into(), path.clone().into()])
+                }
+                ParsedCommand::Move { from, to, .. } => Line::from(vec![
+                    "Move".red().bold(),
+                    " ".into(),
+                    from.clone().into(),
+                    " → ".dim(),
+                    to.clone().into(),
+                ]),
+                ParsedCommand::Read { .. }
+                | ParsedCommand::ListFiles { .. }
+                | ParsedCommand::Search { .. }
+                | ParsedCommand::Unknown { .. } => continue,
+            };
+            push_owned_lines(
+                &adaptive_wrap_line(&line, continuation_opts.clone()),
+                &mut destructive_lines,
+            );
+        }
+        if !destructive_lines.is_empty() {
+            lines.extend(prefix_lines(
+                destructive_lines,
+                Span::from(layout.command_continuation.initial_prefix).dim(),
+                Span::from(layout.command_continuation.subsequent_prefix).dim(),
+            ));
+        }
+
         if let Some(output) = call.output.as_ref() {
             let line_limit = if call.is_user_shell_command() {
                 USER_SHELL_TOOL_CALL_MAX_LINES
@@ -1063,6 +1098,46 @@ mod tests {
         assert_eq!(first, vec!["• Running echo done".to_string()]);
     }
 
+    #[test]
+    fn command_display_calls_out_deletes_and_moves() {
+        let call = ExecCall {
+            call_id: "call-id".to_string(),
+            command: vec!["bash".into(), "-lc".into(), "rm -rf build && mv a b".into()],
+            parsed: vec![
+                ParsedCommand::Delete {
+                    cmd: "rm -rf build".to_string(),
+                    path: "build".to_string(),
+                },
+                ParsedCommand::Move {
+                    cmd: "mv a b".to_string(),
+                    from: "a".to_string(),
+                    to: "b".to_string(),
+                },
+            ],
+            output: None,
+            source: ExecCommandSource::Agent,
+            start_time: Some(Instant::now()),
+            duration: None,
+            interaction_input: None,
+        };
+
+        let cell = ExecCell::new(call, /*animations_enabled*/ false);
+        let rendered: Vec<String> = cell
+            .command_display_lines(/*width*/ 80)
+            .iter()
+            .map(render_line_text)
+            .collect();
+
+        assert_eq!(
+            rendered,
+            vec![
+                "• Running rm -rf build && mv a b".to_string(),
+                "  │ Delete build".to_string(),
+                "  │ Move a → b".to_string(),
+            ]
+        );
+    }
+
     #[test]
     fn exploring_display_does_not_split_long_url_like_search_query() {
         let url_like = "example.test/api/v1/projects/alpha-team/releases/2026-02-17/builds/1234567890/artifacts/reports/performance/summary/detail/with/a/very/long/path";